@@ -0,0 +1,716 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{Entry, Map, Value};
+
+impl Value {
+    /// Looks up a key of an object, `None` for missing keys and non-objects
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    /// Looks up an element of an array, `None` for out of range indices and non-arrays
+    pub fn get_index(&self, index: usize) -> Option<&Value> {
+        match self {
+            Value::Array(array) => array.get(index),
+            _ => None,
+        }
+    }
+
+    /// Looks up a value by RFC 6901 JSON Pointer like `/user/tags/0`, an empty pointer refers to the whole document
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+
+        let tokens = pointer.strip_prefix('/')?.split('/').map(unescape_token);
+        let mut value = self;
+        for token in tokens {
+            value = match value {
+                Value::Object(map) => map.get(&token)?,
+                Value::Array(array) => array.get(parse_index(&token)?)?,
+                _ => return None,
+            };
+        }
+
+        Some(value)
+    }
+
+    /// Mutable version of [`Value::pointer`]
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+
+        let tokens = pointer.strip_prefix('/')?.split('/').map(unescape_token);
+        let mut value = self;
+        for token in tokens {
+            value = match value {
+                Value::Object(map) => map.get_mut(&token)?,
+                Value::Array(array) => array.get_mut(parse_index(&token)?)?,
+                _ => return None,
+            };
+        }
+
+        Some(value)
+    }
+
+    /// Looks up a value by a path built in code, keys are used as they are without escaping
+    pub fn get_path(&self, path: &Path) -> Option<&Value> {
+        path.steps.iter().try_fold(self, |value, step| match (value, step) {
+            (Value::Object(map), Step::Key(key)) => map.get(key),
+            (Value::Array(array), Step::Index(index)) => array.get(*index),
+            _ => None,
+        })
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Integers are converted, possibly losing precision
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(number) => Some(number.as_f64()),
+            Value::RawNumber(text) => text.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Number without a fractional part that fits into `i64`, whole floats like `1e3` included
+    pub fn number_as_i64(&self) -> Option<i64> {
+        let exact = match self {
+            Value::Number(number) => number.as_i64(),
+            Value::RawNumber(text) => text.parse().ok(),
+            _ => return None,
+        };
+
+        // `i64::MAX as f64` rounds up to 2^63, which is already out of range
+        exact.or_else(|| self.whole_f64().filter(|&f| f >= i64::MIN as f64 && f < i64::MAX as f64).map(|f| f as i64))
+    }
+
+    /// Number without a fractional part that fits into `u64`, whole floats like `1e3` included
+    pub fn number_as_u64(&self) -> Option<u64> {
+        let exact = match self {
+            Value::Number(number) => number.as_u64(),
+            Value::RawNumber(text) => text.parse().ok(),
+            _ => return None,
+        };
+
+        // `u64::MAX as f64` rounds up to 2^64, which is already out of range
+        exact.or_else(|| self.whole_f64().filter(|&f| f >= 0.0 && f < u64::MAX as f64).map(|f| f as u64))
+    }
+
+    fn whole_f64(&self) -> Option<f64> {
+        self.as_f64().filter(|f| f.fract() == 0.0)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(boolean) => Some(*boolean),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Handle to a key of an object for updating it in place, see [`Map::entry`], `None` for non-objects
+    pub fn entry(&mut self, key: &str) -> Option<Entry<'_>> {
+        self.as_object_mut().map(|map| map.entry(key.to_owned()))
+    }
+
+    /// Mutable version of [`Value::as_array`], to push or remove elements in place
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<Value>> {
+        match self {
+            Value::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    /// Mutable version of [`Value::as_object`], to insert or remove entries in place
+    pub fn as_object_mut(&mut self) -> Option<&mut Map> {
+        match self {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Name of the JSON type for messages like `expected string, found number`
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Boolean(_) => "boolean",
+            Value::String(_) => "string",
+            Value::Number(_) | Value::RawNumber(_) => "number",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    /// Number of array elements or object entries, `None` for scalars
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            Value::Array(array) => Some(array.len()),
+            Value::Object(map) => Some(map.len()),
+            _ => None,
+        }
+    }
+
+    /// Whether an array or object has no elements, `None` for scalars
+    pub fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Moves the value out, leaving [`Value::Null`] in its place, like [`Option::take`]
+    pub fn take(&mut self) -> Value {
+        std::mem::replace(self, Value::Null)
+    }
+
+    /// Consumes an array into its elements, anything else has none
+    pub fn into_elements(self) -> std::vec::IntoIter<Value> {
+        match self {
+            Value::Array(array) => array.into_iter(),
+            _ => Vec::new().into_iter(),
+        }
+    }
+
+    /// Consumes an object into its key/value pairs, anything else has none
+    pub fn into_entries(self) -> crate::map::IntoIter {
+        match self {
+            Value::Object(map) => map.into_iter(),
+            _ => Map::new().into_iter(),
+        }
+    }
+
+    /// Sorts the keys of every object in the tree, including objects nested in arrays,
+    /// so serializing it afterwards gives the same output for equal values
+    pub fn sort_keys(&mut self) {
+        match self {
+            Value::Array(array) => array.iter_mut().for_each(Value::sort_keys),
+            Value::Object(map) => {
+                map.sort_keys();
+                map.values_mut().for_each(Value::sort_keys);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Path to a value built step by step, like `Path::new().key("user").index(0)`, see [`Value::get_path`]
+///
+/// Keys only match object entries and indices only match array elements.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Path {
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+impl Path {
+    /// Path to the value itself
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.steps.push(Step::Key(key.into()));
+        self
+    }
+
+    pub fn index(mut self, index: usize) -> Self {
+        self.steps.push(Step::Index(index));
+        self
+    }
+}
+
+/// Escapes a key or index for use as a JSON Pointer reference token
+pub(crate) fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Runs `f` with a reference token appended to the pointer, escaped as RFC 6901 requires
+pub(crate) fn with_token(pointer: &mut String, token: &str, f: impl FnOnce(&mut String)) {
+    let length = pointer.len();
+    pointer.push('/');
+    pointer.push_str(&escape_token(token));
+
+    f(pointer);
+    pointer.truncate(length);
+}
+
+fn unescape_token(token: &str) -> String {
+    // order matters, `~01` is `~1` and not `/`
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Array indices in pointers have no leading zeros or signs
+fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+    if !token.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    token.parse().ok()
+}
+
+/// Panics on missing keys and non-objects, see [`Value::get`] for the non-panicking lookup
+impl Index<&str> for Value {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Value {
+        match self {
+            Value::Object(map) => map
+                .get(key)
+                .unwrap_or_else(|| panic!("no key {key:?} in JSON object")),
+            _ => panic!("cannot index non-object JSON value with key {key:?}"),
+        }
+    }
+}
+
+impl IndexMut<&str> for Value {
+    fn index_mut(&mut self, key: &str) -> &mut Value {
+        match self {
+            Value::Object(map) => map
+                .get_mut(key)
+                .unwrap_or_else(|| panic!("no key {key:?} in JSON object")),
+            _ => panic!("cannot index non-object JSON value with key {key:?}"),
+        }
+    }
+}
+
+/// Panics on out of range indices and non-arrays, see [`Value::get_index`] for the non-panicking lookup
+impl Index<usize> for Value {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Value {
+        match self {
+            Value::Array(array) => array.get(index).unwrap_or_else(|| {
+                panic!("index {index} out of range for JSON array of length {}", array.len())
+            }),
+            _ => panic!("cannot index non-array JSON value with index {index}"),
+        }
+    }
+}
+
+impl IndexMut<usize> for Value {
+    fn index_mut(&mut self, index: usize) -> &mut Value {
+        match self {
+            Value::Array(array) => {
+                let len = array.len();
+                array.get_mut(index).unwrap_or_else(|| {
+                    panic!("index {index} out of range for JSON array of length {len}")
+                })
+            }
+            _ => panic!("cannot index non-array JSON value with index {index}"),
+        }
+    }
+}
+
+/// Only strings compare equal to string slices
+impl PartialEq<str> for Value {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for Value {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == Some(*other)
+    }
+}
+
+impl PartialEq<Value> for str {
+    fn eq(&self, other: &Value) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<Value> for &str {
+    fn eq(&self, other: &Value) -> bool {
+        other == self
+    }
+}
+
+/// Only numbers compare equal to `f64`, integers are compared by their numeric value
+impl PartialEq<f64> for Value {
+    fn eq(&self, other: &f64) -> bool {
+        self.as_f64() == Some(*other)
+    }
+}
+
+impl PartialEq<bool> for Value {
+    fn eq(&self, other: &bool) -> bool {
+        self.as_bool() == Some(*other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+    use super::Path;
+
+    fn user() -> Value {
+        Value::object([(
+            "user",
+            Value::object([
+                ("id", Value::number(1)),
+                ("tags", Value::Array(vec![Value::string("admin")])),
+            ]),
+        )])
+    }
+
+    #[test]
+    fn get_nested_key() {
+        let value = user();
+
+        let actual = value.get("user").and_then(|user| user.get("id"));
+
+        assert_eq!(actual, Some(&Value::number(1)));
+    }
+
+    #[test]
+    fn get_missing_key() {
+        assert_eq!(user().get("missing"), None);
+    }
+
+    #[test]
+    fn get_on_non_object() {
+        assert_eq!(Value::Null.get("key"), None);
+        assert_eq!(Value::Array(vec![]).get("key"), None);
+    }
+
+    #[test]
+    fn get_index_in_array() {
+        let value = user();
+
+        let actual = value
+            .get("user")
+            .and_then(|user| user.get("tags"))
+            .and_then(|tags| tags.get_index(0));
+
+        assert_eq!(actual, Some(&Value::string("admin")));
+    }
+
+    #[test]
+    fn get_index_out_of_range_or_non_array() {
+        assert_eq!(Value::Array(vec![Value::Null]).get_index(1), None);
+        assert_eq!(user().get_index(0), None);
+    }
+
+    #[test]
+    fn pointer_nested() {
+        let value = user();
+
+        assert_eq!(value.pointer("/user/tags/0"), Some(&Value::string("admin")));
+        assert_eq!(value.pointer("/user/id"), Some(&Value::number(1)));
+    }
+
+    #[test]
+    fn pointer_empty_is_whole_document() {
+        let value = user();
+
+        assert_eq!(value.pointer(""), Some(&value));
+    }
+
+    #[test]
+    fn pointer_escaped_tokens() {
+        let value = Value::object([
+            ("a/b", Value::number(1)),
+            ("m~n", Value::number(2)),
+            ("~1", Value::number(3)),
+            ("", Value::number(4)),
+        ]);
+
+        assert_eq!(value.pointer("/a~1b"), Some(&Value::number(1)));
+        assert_eq!(value.pointer("/m~0n"), Some(&Value::number(2)));
+        assert_eq!(value.pointer("/~01"), Some(&Value::number(3)));
+        assert_eq!(value.pointer("/"), Some(&Value::number(4)));
+    }
+
+    #[test]
+    fn pointer_missing() {
+        let value = user();
+
+        assert_eq!(value.pointer("user"), None);
+        assert_eq!(value.pointer("/missing"), None);
+        assert_eq!(value.pointer("/user/tags/1"), None);
+        assert_eq!(value.pointer("/user/tags/00"), None);
+        assert_eq!(value.pointer("/user/tags/+0"), None);
+        assert_eq!(value.pointer("/user/tags/-"), None);
+        assert_eq!(value.pointer("/user/id/0"), None);
+    }
+
+    #[test]
+    fn pointer_mut_replaces_value() {
+        let mut value = user();
+
+        *value.pointer_mut("/user/tags/0").unwrap() = Value::string("guest");
+        *value.pointer_mut("/user/id").unwrap() = Value::number(2);
+
+        assert_eq!(value["user"]["tags"][0], Value::string("guest"));
+        assert_eq!(value["user"]["id"], Value::number(2));
+    }
+
+    #[test]
+    fn pointer_mut_whole_document() {
+        let mut value = user();
+
+        *value.pointer_mut("").unwrap() = Value::Null;
+
+        assert_eq!(value, Value::Null);
+    }
+
+    #[test]
+    fn pointer_mut_missing() {
+        let mut value = user();
+
+        assert_eq!(value.pointer_mut("/user/missing"), None);
+        assert_eq!(value.pointer_mut("/user/tags/1"), None);
+        assert_eq!(value.pointer_mut("/user/id/0"), None);
+    }
+
+    #[test]
+    fn type_names() {
+        let value = user();
+
+        assert_eq!(value.type_name(), "object");
+        assert_eq!(value["user"]["tags"].type_name(), "array");
+        assert_eq!(value["user"]["id"].type_name(), "number");
+        assert_eq!(Value::RawNumber(String::from("1.0")).type_name(), "number");
+        assert_eq!(Value::string("a").type_name(), "string");
+        assert_eq!(Value::Boolean(false).type_name(), "boolean");
+        assert_eq!(Value::Null.type_name(), "null");
+    }
+
+    #[test]
+    fn len_of_containers() {
+        let value = user();
+
+        assert_eq!(value.len(), Some(1));
+        assert_eq!(value["user"].len(), Some(2));
+        assert_eq!(value["user"]["tags"].len(), Some(1));
+        assert_eq!(value.is_empty(), Some(false));
+        assert_eq!(Value::Array(Vec::new()).is_empty(), Some(true));
+        assert_eq!(Value::object([]).is_empty(), Some(true));
+    }
+
+    #[test]
+    fn len_of_scalars() {
+        assert_eq!(Value::string("abc").len(), None);
+        assert_eq!(Value::number(3).len(), None);
+        assert_eq!(Value::Null.is_empty(), None);
+    }
+
+    #[test]
+    fn take_moves_subtree_out() {
+        let mut value = user();
+
+        let tags = value["user"]["tags"].take();
+
+        assert_eq!(tags, Value::Array(vec![Value::string("admin")]));
+        assert_eq!(value["user"]["tags"], Value::Null);
+
+        let mut id = Value::number(1);
+        assert_eq!(id.take(), Value::number(1));
+        assert!(id.is_null());
+    }
+
+    #[test]
+    fn number_as_integers() {
+        assert_eq!(Value::number(-7).number_as_i64(), Some(-7));
+        assert_eq!(Value::number(7).number_as_u64(), Some(7));
+        assert_eq!(Value::number(1e3).number_as_i64(), Some(1000));
+        assert_eq!(Value::number(-0.0).number_as_u64(), Some(0));
+        assert_eq!(Value::RawNumber(String::from("9007199254740993")).number_as_i64(), Some(9007199254740993));
+        assert_eq!(Value::RawNumber(String::from("2.0E1")).number_as_u64(), Some(20));
+    }
+
+    #[test]
+    fn number_as_integers_rejects_fractions_and_overflow() {
+        assert_eq!(Value::number(1.5).number_as_i64(), None);
+        assert_eq!(Value::number(-1).number_as_u64(), None);
+        assert_eq!(Value::number(u64::MAX).number_as_i64(), None);
+        assert_eq!(Value::number(9.223372036854776e18).number_as_i64(), None);
+        assert_eq!(Value::number(1.8446744073709552e19).number_as_u64(), None);
+        assert_eq!(Value::number(f64::NAN).number_as_i64(), None);
+        assert_eq!(Value::number(f64::INFINITY).number_as_u64(), None);
+        assert_eq!(Value::string("1").number_as_i64(), None);
+    }
+
+    #[test]
+    fn typed_accessors() {
+        assert_eq!(Value::string("a").as_str(), Some("a"));
+        assert_eq!(Value::number(1).as_f64(), Some(1.0));
+        assert_eq!(Value::number(0.5).as_f64(), Some(0.5));
+        assert_eq!(Value::RawNumber(String::from("1.10")).as_f64(), Some(1.1));
+        assert_eq!(Value::Boolean(true).as_bool(), Some(true));
+        assert_eq!(Value::Array(vec![Value::Null]).as_array(), Some(&vec![Value::Null]));
+        assert_eq!(user()["user"].as_object().map(|map| map.len()), Some(2));
+        assert!(Value::Null.is_null());
+    }
+
+    #[test]
+    fn typed_accessors_on_mismatch() {
+        let value = Value::Boolean(false);
+
+        assert_eq!(value.as_str(), None);
+        assert_eq!(value.as_f64(), None);
+        assert_eq!(Value::Null.as_bool(), None);
+        assert_eq!(value.as_array(), None);
+        assert_eq!(value.as_object(), None);
+        assert!(!value.is_null());
+    }
+
+    #[test]
+    fn mutable_accessors() {
+        let mut value = user();
+
+        value["user"]["tags"].as_array_mut().unwrap().push(Value::string("dev"));
+        value["user"].as_object_mut().unwrap().insert(String::from("active"), Value::Boolean(true));
+
+        assert_eq!(value.pointer("/user/tags/1"), Some(&Value::string("dev")));
+        assert_eq!(value.pointer("/user/active"), Some(&Value::Boolean(true)));
+        assert_eq!(value["user"]["id"].as_array_mut(), None);
+        assert_eq!(value["user"]["id"].as_object_mut(), None);
+    }
+
+    #[test]
+    fn entry_counts_in_place() {
+        let mut value = Value::object([("count", Value::number(1))]);
+
+        for key in ["count", "count", "other"] {
+            let count = value.entry(key).unwrap().or_insert(Value::number(0));
+            *count = Value::number(count.as_f64().unwrap() + 1.0);
+        }
+
+        assert_eq!(value, Value::object([("count", Value::number(3)), ("other", Value::number(1))]));
+        assert!(Value::Array(Vec::new()).entry("count").is_none());
+    }
+
+    #[test]
+    fn compare_with_primitives() {
+        let value = user();
+
+        assert!(value["user"]["tags"][0] == "admin");
+        assert!("admin" == value["user"]["tags"][0]);
+        assert!(value["user"]["tags"][0] == *"admin");
+        assert!(value["user"]["id"] == 1.0);
+        assert!(Value::Boolean(true) == true);
+    }
+
+    #[test]
+    fn compare_with_primitives_on_mismatch() {
+        assert!(Value::string("1") != 1.0);
+        assert!(Value::number(1) != "1");
+        assert!(Value::Null != false);
+        assert!(Value::string("true") != true);
+        assert!(Value::string("admin") != "Admin");
+    }
+
+    #[test]
+    fn index_chain() {
+        let value = user();
+
+        assert_eq!(value["user"]["tags"][0], Value::string("admin"));
+    }
+
+    #[test]
+    fn get_path() {
+        let value = json!({"user": {"tags": ["admin"], "a/b": 1, "0": true}});
+
+        assert_eq!(value.get_path(&Path::new()), Some(&value));
+        assert_eq!(value.get_path(&Path::new().key("user").key("tags").index(0)), Some(&Value::string("admin")));
+        assert_eq!(value.get_path(&Path::new().key("user").key("a/b")), Some(&Value::number(1)));
+        assert_eq!(value.get_path(&Path::new().key("user").key("0")), Some(&Value::Boolean(true)));
+        assert_eq!(value.get_path(&Path::new().key("user").index(0)), None);
+        assert_eq!(value.get_path(&Path::new().key("user").key("tags").key("0")), None);
+        assert_eq!(value.get_path(&Path::new().key("missing")), None);
+    }
+
+    #[test]
+    fn into_elements() {
+        let elements: Vec<Value> = json!([1, "a"]).into_elements().collect();
+
+        assert_eq!(elements, vec![Value::number(1), Value::string("a")]);
+        assert_eq!(json!({"a": 1}).into_elements().count(), 0);
+        assert_eq!(Value::Null.into_elements().count(), 0);
+    }
+
+    #[test]
+    fn into_entries() {
+        let mut entries = Vec::new();
+        for (key, value) in json!({"a": [1]}).into_entries() {
+            entries.push((key, value));
+        }
+
+        assert_eq!(entries, vec![(String::from("a"), json!([1]))]);
+        assert_eq!(json!({"a": 1, "b": 2}).into_entries().next_back(), Some((String::from("b"), json!(2))));
+        assert_eq!(json!([1]).into_entries().len(), 0);
+        assert_eq!(Value::string("a").into_entries().count(), 0);
+    }
+
+    #[test]
+    fn sort_keys_recursively() {
+        let mut value = crate::parse(String::from(r#"{"b": [{"z": 1, "y": 2}], "a": {"d": null, "c": {"f": 1, "e": 2}}}"#)).unwrap();
+
+        value.sort_keys();
+
+        assert_eq!(value.to_string(), r#"{"a":{"c":{"e":2,"f":1},"d":null},"b":[{"y":2,"z":1}]}"#);
+    }
+
+    #[test]
+    fn index_mut_replaces_value() {
+        let mut value = user();
+
+        value["user"]["tags"][0] = Value::Null;
+        value["user"]["id"] = Value::number(2);
+
+        assert_eq!(value["user"]["tags"][0], Value::Null);
+        assert_eq!(value["user"]["id"], Value::number(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "no key \"missing\" in JSON object")]
+    fn index_missing_key() {
+        let _ = &user()["missing"];
+    }
+
+    #[test]
+    #[should_panic(expected = "index 1 out of range for JSON array of length 1")]
+    fn index_out_of_range() {
+        let _ = &user()["user"]["tags"][1];
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot index non-array JSON value with index 0")]
+    fn index_non_array() {
+        let _ = &Value::Null[0];
+    }
+}
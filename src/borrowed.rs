@@ -0,0 +1,443 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::parse::{count_element, parse_property_name, unexpected_token, StreamToken, TokenParseError, TokenStream};
+use crate::tokenize::{ByteCursor, RawToken, Token, TokenizeError, Tokenizer};
+use crate::{DuplicateKeyPolicy, Map, Number, ParseError, ParseOptions, Span, Value};
+
+/// JSON value whose strings borrow from the input, only strings with escapes are allocated
+///
+/// Objects keep their entries in the order they were written, a repeated key is
+/// handled as [`ParseOptions::duplicate_keys`] says, like it is for [`Map`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueRef<'a> {
+    Null,
+    Boolean(bool),
+    String(Cow<'a, str>),
+    Number(Number),
+    /// Number literal kept exactly as written, see [`ParseOptions::preserve_number_text`]
+    RawNumber(String),
+    Array(Vec<ValueRef<'a>>),
+    Object(Vec<(Cow<'a, str>, ValueRef<'a>)>),
+}
+
+/// Parses a single value, borrowing strings without escapes from input
+pub fn parse_borrowed(input: &str) -> Result<ValueRef<'_>, ParseError> {
+    parse_borrowed_with(input, &ParseOptions::default())
+}
+
+pub fn parse_borrowed_with<'a>(input: &'a str, options: &ParseOptions) -> Result<ValueRef<'a>, ParseError> {
+    let mut tokens = document_tokens(input, options)?;
+    let value = parse_value(&mut tokens, options)?;
+
+    if tokens.next()?.is_some() {
+        return Err(ParseError::TrailingTokens);
+    }
+
+    Ok(value)
+}
+
+impl<'a> ValueRef<'a> {
+    /// Looks up a key of an object, `None` for missing keys and non-objects
+    pub fn get(&self, key: &str) -> Option<&ValueRef<'a>> {
+        match self {
+            ValueRef::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ValueRef::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Copies the borrowed strings into an owned [`Value`]
+    pub fn into_owned(self) -> Value {
+        match self {
+            ValueRef::Null => Value::Null,
+            ValueRef::Boolean(boolean) => Value::Boolean(boolean),
+            ValueRef::String(string) => Value::String(string.into_owned()),
+            ValueRef::Number(number) => Value::Number(number),
+            ValueRef::RawNumber(text) => Value::RawNumber(text),
+            ValueRef::Array(array) => Value::Array(array.into_iter().map(ValueRef::into_owned).collect()),
+            ValueRef::Object(entries) => Value::Object(
+                entries.into_iter().map(|(key, value)| (key.into_owned(), value.into_owned())).collect::<Map>(),
+            ),
+        }
+    }
+}
+
+impl From<ValueRef<'_>> for Value {
+    fn from(value: ValueRef<'_>) -> Self {
+        value.into_owned()
+    }
+}
+
+/// Owned value whose strings are kept as [`Cow::Owned`]
+impl From<Value> for ValueRef<'_> {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => ValueRef::Null,
+            Value::Boolean(boolean) => ValueRef::Boolean(boolean),
+            Value::String(string) => ValueRef::String(Cow::Owned(string)),
+            Value::Number(number) => ValueRef::Number(number),
+            Value::RawNumber(text) => ValueRef::RawNumber(text),
+            Value::Array(array) => ValueRef::Array(array.into_iter().map(ValueRef::from).collect()),
+            Value::Object(map) => {
+                ValueRef::Object(map.into_iter().map(|(key, value)| (Cow::Owned(key), value.into())).collect())
+            }
+        }
+    }
+}
+
+/// Token whose string, if it is one, borrows from the input where it can
+pub(crate) enum TokenRef<'a> {
+    String(Cow<'a, str>),
+    Other(Token),
+}
+
+impl<'a> StreamToken for (TokenRef<'a>, Span) {
+    type Str = Cow<'a, str>;
+
+    fn span(&self) -> Span {
+        self.1
+    }
+
+    fn is(&self, token: &Token) -> bool {
+        matches!(&self.0, TokenRef::Other(other) if other == token)
+    }
+
+    fn into_string(self) -> Result<Cow<'a, str>, Self> {
+        match self {
+            (TokenRef::String(string), _) => Ok(string),
+            token => Err(token),
+        }
+    }
+
+    fn to_token(&self) -> Token {
+        match &self.0 {
+            TokenRef::String(string) => Token::String(string.clone().into_owned()),
+            TokenRef::Other(token) => token.clone(),
+        }
+    }
+}
+
+/// What [`Tokens`] yields
+pub(crate) type TokenResult<'a> = Result<(TokenRef<'a>, Span), TokenizeError>;
+
+/// Tokens of the input, with strings that have no escapes sliced out of it
+struct Tokens<'a> {
+    input: &'a str,
+    tokenizer: Tokenizer<ByteCursor<'a>>,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = TokenResult<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (token, span) = match self.tokenizer.next_raw()? {
+            Ok(raw) => raw,
+            Err(error) => return Some(Err(error)),
+        };
+        let token = match token {
+            RawToken::Token(Token::String(string)) => TokenRef::String(Cow::Owned(string)),
+            RawToken::Token(token) => TokenRef::Other(token),
+            // without the quotes
+            RawToken::Verbatim => {
+                TokenRef::String(Cow::Borrowed(&self.input[span.start.byte_offset + 1..span.end.byte_offset - 1]))
+            }
+        };
+
+        Some(Ok((token, span)))
+    }
+}
+
+/// Stream over the tokens of a whole document like `parse::document_tokens`,
+/// but with strings that have no escapes sliced out of input
+pub(crate) fn document_tokens<'a>(
+    input: &'a str,
+    options: &ParseOptions,
+) -> Result<TokenStream<impl Iterator<Item = TokenResult<'a>>>, ParseError> {
+    let mut source = Tokens { input, tokenizer: Tokenizer::new(input).with_options(options) };
+    let Some(first) = source.next() else {
+        return Err(ParseError::no_value(source.tokenizer.location()));
+    };
+
+    Ok(TokenStream::new(std::iter::once(first).chain(source)))
+}
+
+/// Container that is still being parsed
+///
+/// Both count the elements started so far, see `ParseOptions::max_elements`.
+enum Frame<'a> {
+    Array(Vec<ValueRef<'a>>, usize),
+    /// Object together with the key of the value being parsed and where each key is in the entries
+    Object(Vec<(Cow<'a, str>, ValueRef<'a>)>, Cow<'a, str>, usize, HashMap<Cow<'a, str>, usize>),
+}
+
+/// Same explicit stack of open containers as the owned parser, see `parse::parse_value`
+fn parse_value<'a, I>(tokens: &mut TokenStream<I>, options: &ParseOptions) -> Result<ValueRef<'a>, ParseError>
+where
+    I: Iterator<Item = TokenResult<'a>>,
+{
+    let mut stack: Vec<Frame<'a>> = Vec::new();
+
+    'value: loop {
+        let (token, span) = tokens.next_token()?;
+
+        if matches!(token, TokenRef::Other(Token::LeftBracket | Token::LeftBrace)) && stack.len() >= options.max_depth {
+            return Err(TokenParseError::DepthLimitExceeded { location: span.start }.into());
+        }
+
+        let mut value = match token {
+            TokenRef::String(string) => ValueRef::String(string),
+            TokenRef::Other(Token::Null) => ValueRef::Null,
+            TokenRef::Other(Token::True) => ValueRef::Boolean(true),
+            TokenRef::Other(Token::False) => ValueRef::Boolean(false),
+            TokenRef::Other(Token::Number(number)) => ValueRef::Number(number),
+            TokenRef::Other(Token::RawNumber(text)) => match options.number_parser {
+                Some(number_parser) => number_parser(&text)
+                    .map_err(|message| TokenParseError::NumberParserFailed { message, location: span.start })?
+                    .into(),
+                None => ValueRef::RawNumber(text),
+            },
+            TokenRef::Other(Token::LeftBracket) => {
+                if tokens.peek_is(&Token::RightBracket)? {
+                    tokens.next()?;
+                    ValueRef::Array(Vec::new())
+                } else {
+                    count_element(&mut 0, tokens, options)?;
+                    stack.push(Frame::Array(Vec::new(), 1));
+                    continue 'value;
+                }
+            }
+            TokenRef::Other(Token::LeftBrace) => {
+                if tokens.peek_is(&Token::RightBrace)? {
+                    tokens.next()?;
+                    ValueRef::Object(Vec::new())
+                } else {
+                    count_element(&mut 0, tokens, options)?;
+                    let (key, _) = parse_property_name(tokens)?;
+                    stack.push(Frame::Object(Vec::new(), key, 1, HashMap::new()));
+                    continue 'value;
+                }
+            }
+            TokenRef::Other(token) => {
+                return Err(unexpected_token(token, span.start, stack.is_empty()).into())
+            }
+        };
+
+        // hand the finished value to its container, closing every container that ends with it
+        loop {
+            let close = match stack.last_mut() {
+                None => return Ok(value),
+                Some(Frame::Array(array, _)) => {
+                    array.push(value);
+                    Token::RightBracket
+                }
+                Some(Frame::Object(entries, key, _, positions)) => {
+                    let key = std::mem::take(key);
+                    match positions.get(&key) {
+                        Some(_) if options.duplicate_keys == DuplicateKeyPolicy::FirstWins => {}
+                        Some(&i) => entries[i].1 = value,
+                        None => {
+                            positions.insert(key.clone(), entries.len());
+                            entries.push((key, value));
+                        }
+                    }
+                    Token::RightBrace
+                }
+            };
+
+            let (token, span) = tokens.next_token()?;
+            match token {
+                TokenRef::Other(Token::Comma) if !tokens.peek_is(&close)? => {
+                    match stack.last_mut() {
+                        Some(Frame::Array(_, length)) => count_element(length, tokens, options)?,
+                        Some(Frame::Object(_, key, length, positions)) => {
+                            count_element(length, tokens, options)?;
+                            let (next, location) = parse_property_name(tokens)?;
+                            if options.duplicate_keys == DuplicateKeyPolicy::Error && positions.contains_key(&next) {
+                                return Err(TokenParseError::DuplicateKey { key: next.into_owned(), location }.into());
+                            }
+
+                            *key = next;
+                        }
+                        None => unreachable!("a value was just handed to its container"),
+                    }
+                    continue 'value;
+                }
+                TokenRef::Other(Token::Comma) if options.allow_trailing_commas => {
+                    tokens.next()?;
+                }
+                TokenRef::Other(Token::Comma) => {
+                    return Err(TokenParseError::TrailingComma { location: span.start }.into())
+                }
+                TokenRef::Other(token) if token == close => {}
+                found => {
+                    let found = (found, span).to_token();
+                    return Err(TokenParseError::ExpectedComma { found, location: span.start }.into());
+                }
+            }
+
+            value = match stack.pop() {
+                Some(Frame::Array(array, _)) => ValueRef::Array(array),
+                Some(Frame::Object(entries, ..)) => ValueRef::Object(entries),
+                None => unreachable!("a container was just closed"),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::parse::TokenParseError;
+    use crate::tokenize::Token;
+    use crate::{parse, DuplicateKeyPolicy, Location, Number, ParseError, ParseOptions, TokenizeError, Value};
+    use super::{parse_borrowed, parse_borrowed_with, ValueRef};
+
+    #[test]
+    fn borrows_strings_without_escapes() {
+        let input = r#"{"name": "plain", "tags": ["a", "b\nc"]}"#;
+
+        let actual = parse_borrowed(input).unwrap();
+
+        let ValueRef::Object(entries) = &actual else { panic!("expected an object") };
+        assert!(matches!(entries[0], (Cow::Borrowed("name"), ValueRef::String(Cow::Borrowed("plain")))));
+        assert_eq!(
+            entries[1].1,
+            ValueRef::Array(vec![ValueRef::String(Cow::Borrowed("a")), ValueRef::String(Cow::Owned(String::from("b\nc")))]),
+        );
+        assert!(matches!(&entries[1].1, ValueRef::Array(tags) if matches!(tags[1], ValueRef::String(Cow::Owned(_)))));
+    }
+
+    #[test]
+    fn matches_owned_parse() {
+        let input = r#"{"a": [1, -2.5, true, null, {}], "b": "xé", "c": {"d": []}}"#;
+
+        let actual = parse_borrowed(input).unwrap();
+
+        assert_eq!(actual.get("b").and_then(ValueRef::as_str), Some("xé"));
+        assert_eq!(actual.get("a"), Some(&ValueRef::Array(vec![
+            ValueRef::Number(Number::from(1)),
+            ValueRef::Number(Number::from(-2.5)),
+            ValueRef::Boolean(true),
+            ValueRef::Null,
+            ValueRef::Object(Vec::new()),
+        ])));
+        assert_eq!(actual.into_owned(), parse(String::from(input)).unwrap());
+    }
+
+    #[test]
+    fn repeated_key_replaces_value() {
+        let actual = parse_borrowed(r#"{"a": 1, "b": 2, "a": 3}"#).unwrap();
+
+        assert_eq!(actual, ValueRef::Object(vec![
+            (Cow::Borrowed("a"), ValueRef::Number(Number::from(3))),
+            (Cow::Borrowed("b"), ValueRef::Number(Number::from(2))),
+        ]));
+    }
+
+    #[test]
+    fn duplicate_key_policies() {
+        let input = r#"{"a": 1, "a": 2}"#;
+        let first_wins = ParseOptions { duplicate_keys: DuplicateKeyPolicy::FirstWins, ..ParseOptions::default() };
+        let error = ParseOptions { duplicate_keys: DuplicateKeyPolicy::Error, ..ParseOptions::default() };
+
+        assert_eq!(parse_borrowed_with(input, &first_wins).unwrap().get("a"), Some(&ValueRef::Number(Number::from(1))));
+        assert_eq!(
+            parse_borrowed_with(input, &error).unwrap_err(),
+            TokenParseError::DuplicateKey { key: String::from("a"), location: Location::new(1, 10, 9) }.into(),
+        );
+    }
+
+    #[test]
+    fn large_object() {
+        let keys = 100_000;
+        let entries: Vec<String> = (0..keys).map(|i| format!("\"k{i}\": {i}, \"k{}\": 0", i % 10)).collect();
+        let input = format!("{{{}}}", entries.join(", "));
+
+        let ValueRef::Object(entries) = parse_borrowed(&input).unwrap() else { panic!("expected an object") };
+
+        assert_eq!(entries.len(), keys);
+        assert_eq!(entries[1], (Cow::Borrowed("k1"), ValueRef::Number(Number::from(0))));
+    }
+
+    #[test]
+    fn number_parser() {
+        let options = ParseOptions { number_parser: Some(|text| Ok(Value::String(text.into()))), ..ParseOptions::default() };
+
+        let actual = parse_borrowed_with("[1.50]", &options).unwrap();
+
+        assert_eq!(actual, ValueRef::Array(vec![ValueRef::String(Cow::Owned(String::from("1.50")))]));
+    }
+
+    #[test]
+    fn empty_and_single_quoted_strings() {
+        let options = ParseOptions { allow_single_quotes: true, ..ParseOptions::default() };
+
+        let actual = parse_borrowed_with(r#"['', "", 'it\'s']"#, &options).unwrap();
+
+        assert_eq!(actual, ValueRef::Array(vec![
+            ValueRef::String(Cow::Borrowed("")),
+            ValueRef::String(Cow::Borrowed("")),
+            ValueRef::String(Cow::Owned(String::from("it's"))),
+        ]));
+    }
+
+    #[test]
+    fn options_apply() {
+        let options = ParseOptions { allow_trailing_commas: true, allow_unquoted_keys: true, ..ParseOptions::default() };
+
+        let actual = parse_borrowed_with("{key: [1,],}", &options).unwrap();
+
+        assert_eq!(actual.get("key"), Some(&ValueRef::Array(vec![ValueRef::Number(Number::from(1))])));
+    }
+
+    #[test]
+    fn element_limit() {
+        let options = ParseOptions { max_elements: Some(2), ..ParseOptions::default() };
+
+        assert!(parse_borrowed_with(r#"[1, {"a": 1, "b": 2}]"#, &options).is_ok());
+        assert_eq!(
+            parse_borrowed_with(r#"{"a": 1, "b": 2, "c": 3}"#, &options).unwrap_err(),
+            TokenParseError::TooManyElements { location: Location::new(1, 18, 17) }.into(),
+        );
+        assert_eq!(
+            parse_borrowed_with("[1, 2, 3]", &ParseOptions { max_elements: Some(0), ..options.clone() }).unwrap_err(),
+            TokenParseError::TooManyElements { location: Location::new(1, 2, 1) }.into(),
+        );
+        assert_eq!(
+            parse_borrowed_with("[", &ParseOptions { max_elements: Some(0), ..options }).unwrap_err(),
+            TokenParseError::UnexpectedEof.into(),
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse_borrowed("[1 2]").unwrap_err(),
+            TokenParseError::ExpectedComma { found: Token::number(2), location: Location::new(1, 4, 3) }.into(),
+        );
+        assert_eq!(
+            parse_borrowed(r#"{"a": 1,}"#).unwrap_err(),
+            TokenParseError::TrailingComma { location: Location::new(1, 8, 7) }.into(),
+        );
+        assert_eq!(
+            parse_borrowed(r#"{"a" "b"}"#).unwrap_err(),
+            TokenParseError::ExpectedColon { found: Token::string("b"), location: Location::new(1, 6, 5) }.into(),
+        );
+        assert_eq!(parse_borrowed("[").unwrap_err(), TokenParseError::UnexpectedEof.into());
+        assert_eq!(
+            parse_borrowed(r#"["\q"]"#).unwrap_err(),
+            TokenizeError::InvalidEscape { ch: 'q', location: Location::new(1, 3, 2) }.into(),
+        );
+        assert_eq!(parse_borrowed("1 2").unwrap_err(), ParseError::TrailingTokens);
+        assert_eq!(parse_borrowed("").unwrap_err(), ParseError::EmptyInput);
+        assert_eq!(parse_borrowed(" ").unwrap_err(), ParseError::BlankInput);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::Value;
+
+impl Value {
+    /// Compares objects by their entries regardless of key order and arrays element by element in order
+    ///
+    /// This is what `==` does as well.
+    pub fn structurally_eq(&self, other: &Value) -> bool {
+        equal(self, other, false)
+    }
+
+    /// Like [`Value::structurally_eq`], but arrays are equal when they hold the same elements
+    /// the same number of times in any order, at every level
+    pub fn eq_unordered_arrays(&self, other: &Value) -> bool {
+        equal(self, other, true)
+    }
+}
+
+fn equal(a: &Value, b: &Value, unordered_arrays: bool) -> bool {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter().all(|(key, a)| b.get(key).is_some_and(|b| equal(a, b, unordered_arrays)))
+        }
+        (Value::Array(a), Value::Array(b)) if a.len() != b.len() => false,
+        (Value::Array(a), Value::Array(b)) if unordered_arrays => {
+            // the comparison is an equivalence, so matching every element to the first equal one left works
+            let mut unmatched: Vec<&Value> = b.iter().collect();
+            a.iter().all(|a| match unmatched.iter().position(|b| equal(a, b, true)) {
+                Some(i) => {
+                    unmatched.swap_remove(i);
+                    true
+                }
+                None => false,
+            })
+        }
+        (Value::Array(a), Value::Array(b)) => a.iter().zip(b).all(|(a, b)| equal(a, b, false)),
+        (a, b) => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn structural_equality() {
+        let value = json!({"a": 1, "b": [1, {"x": null, "y": true}]});
+
+        assert!(value.structurally_eq(&json!({"b": [1, {"y": true, "x": null}], "a": 1.0})));
+        assert!(!value.structurally_eq(&json!({"a": 1, "b": [{"x": null, "y": true}, 1]})));
+        assert!(!value.structurally_eq(&json!({"a": 1, "b": [1, {"x": null}]})));
+        assert!(!value.structurally_eq(&json!({"a": 1})));
+    }
+
+    #[test]
+    fn unordered_arrays() {
+        let value = json!({"tags": ["a", "b", "a"], "nested": [[1, 2], [3]]});
+
+        assert!(value.eq_unordered_arrays(&json!({"nested": [[3], [2, 1]], "tags": ["b", "a", "a"]})));
+        assert!(!value.eq_unordered_arrays(&json!({"nested": [[3], [2, 1]], "tags": ["b", "b", "a"]})));
+        assert!(!value.eq_unordered_arrays(&json!({"nested": [[3], [2, 1]], "tags": ["b", "a"]})));
+        assert!(json!(["x", 1]).eq_unordered_arrays(&json!([1, "x"])));
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use crate::{Map, Number, Value};
+
+impl From<&str> for Value {
+    fn from(string: &str) -> Self {
+        Value::String(String::from(string))
+    }
+}
+
+impl From<String> for Value {
+    fn from(string: String) -> Self {
+        Value::String(string)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(boolean: bool) -> Self {
+        Value::Boolean(boolean)
+    }
+}
+
+impl From<Number> for Value {
+    fn from(number: Number) -> Self {
+        Value::Number(number)
+    }
+}
+
+macro_rules! from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(n: $ty) -> Self {
+                    Value::Number(Number::from(n))
+                }
+            }
+        )*
+    };
+}
+
+from_number!(f32, f64, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<Vec<Value>> for Value {
+    fn from(array: Vec<Value>) -> Self {
+        Value::Array(array)
+    }
+}
+
+impl From<Map> for Value {
+    fn from(map: Map) -> Self {
+        Value::Object(map)
+    }
+}
+
+/// Entries end up in the iteration order of the `HashMap`, which is unspecified
+impl From<HashMap<String, Value>> for Value {
+    fn from(map: HashMap<String, Value>) -> Self {
+        Value::Object(map.into_iter().collect())
+    }
+}
+
+/// Consuming conversions give the value back unchanged when it is of another type
+impl Value {
+    pub fn try_into_string(self) -> Result<String, Value> {
+        match self {
+            Value::String(string) => Ok(string),
+            value => Err(value),
+        }
+    }
+
+    /// Integers are converted, possibly losing precision, like [`Value::as_f64`]
+    pub fn try_into_f64(self) -> Result<f64, Value> {
+        self.as_f64().ok_or(self)
+    }
+
+    pub fn try_into_bool(self) -> Result<bool, Value> {
+        match self {
+            Value::Boolean(boolean) => Ok(boolean),
+            value => Err(value),
+        }
+    }
+
+    pub fn try_into_array(self) -> Result<Vec<Value>, Value> {
+        match self {
+            Value::Array(array) => Ok(array),
+            value => Err(value),
+        }
+    }
+
+    pub fn try_into_object(self) -> Result<Map, Value> {
+        match self {
+            Value::Object(map) => Ok(map),
+            value => Err(value),
+        }
+    }
+}
+
+macro_rules! try_from_value {
+    ($($ty:ty => $method:ident),*) => {
+        $(
+            impl TryFrom<Value> for $ty {
+                type Error = Value;
+
+                fn try_from(value: Value) -> Result<Self, Value> {
+                    value.$method()
+                }
+            }
+        )*
+    };
+}
+
+try_from_value!(
+    String => try_into_string,
+    f64 => try_into_f64,
+    bool => try_into_bool,
+    Vec<Value> => try_into_array,
+    Map => try_into_object
+);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{Map, Number, Value};
+
+    #[test]
+    fn from_strings() {
+        assert_eq!(Value::from("a"), Value::string("a"));
+        assert_eq!(Value::from(String::from("a")), Value::string("a"));
+    }
+
+    #[test]
+    fn from_numbers() {
+        assert_eq!(Value::from(1.5), Value::number(1.5));
+        assert_eq!(Value::from(-3i64), Value::number(-3));
+        assert_eq!(Value::from(7), Value::number(7));
+        assert_eq!(Value::from(u64::MAX), Value::number(u64::MAX));
+        assert_eq!(Value::from(Number::from(2)), Value::number(2));
+    }
+
+    #[test]
+    fn from_bool() {
+        assert_eq!(Value::from(true), Value::Boolean(true));
+    }
+
+    #[test]
+    fn from_containers() {
+        assert_eq!(
+            Value::from(vec![Value::from(1), Value::from("a")]),
+            Value::Array(vec![Value::number(1), Value::string("a")]),
+        );
+        assert_eq!(Value::from(Map::new()), Value::object([]));
+    }
+
+    #[test]
+    fn from_hash_map() {
+        let map = HashMap::from([
+            (String::from("a"), Value::Null),
+            (String::from("b"), Value::from(true)),
+        ]);
+
+        let actual = Value::from(map);
+
+        assert_eq!(actual, Value::object([("a", Value::Null), ("b", Value::Boolean(true))]));
+    }
+
+    #[test]
+    fn try_into_moves_value_out() {
+        assert_eq!(Value::string("a").try_into_string(), Ok(String::from("a")));
+        assert_eq!(Value::number(2).try_into_f64(), Ok(2.0));
+        assert_eq!(Value::RawNumber(String::from("1.5")).try_into_f64(), Ok(1.5));
+        assert_eq!(Value::Boolean(true).try_into_bool(), Ok(true));
+        assert_eq!(Value::Array(vec![Value::Null]).try_into_array(), Ok(vec![Value::Null]));
+        assert_eq!(Value::object([]).try_into_object(), Ok(Map::new()));
+    }
+
+    #[test]
+    fn try_into_returns_mismatch() {
+        assert_eq!(Value::number(1).try_into_string(), Err(Value::number(1)));
+        assert_eq!(Value::string("1").try_into_f64(), Err(Value::string("1")));
+        assert_eq!(Value::Null.try_into_bool(), Err(Value::Null));
+        assert_eq!(Value::object([]).try_into_array(), Err(Value::object([])));
+        assert_eq!(Value::Array(vec![]).try_into_object(), Err(Value::Array(vec![])));
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(String::try_from(Value::string("a")), Ok(String::from("a")));
+        assert_eq!(f64::try_from(Value::number(0.5)), Ok(0.5));
+
+        let result: Result<bool, Value> = Value::string("x").try_into();
+        assert_eq!(result, Err(Value::string("x")));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::access::with_token;
+use crate::{Map, Value};
+
+impl Value {
+    /// Computes the RFC 6902 JSON Patch, an array of operations, that turns this value into `other`
+    ///
+    /// Objects are compared key by key and arrays element by element at the same index,
+    /// elements past the end of the shorter array are added or removed. Any other difference
+    /// replaces the value as a whole, equal values produce an empty patch.
+    pub fn diff(&self, other: &Value) -> Value {
+        let mut operations = Vec::new();
+        diff(self, other, &mut String::new(), &mut operations);
+
+        Value::Array(operations)
+    }
+}
+
+/// Appends the operations for the value at `path`, which is restored before returning
+fn diff(from: &Value, to: &Value, path: &mut String, operations: &mut Vec<Value>) {
+    match (from, to) {
+        (Value::Object(from), Value::Object(to)) => {
+            for (key, value) in from {
+                with_token(path, key, |path| match to.get(key) {
+                    Some(target) => diff(value, target, path, operations),
+                    None => operations.push(operation("remove", path, None)),
+                });
+            }
+            for (key, value) in to.iter().filter(|(key, _)| !from.contains_key(key)) {
+                with_token(path, key, |path| operations.push(operation("add", path, Some(value))));
+            }
+        }
+        (Value::Array(from), Value::Array(to)) => {
+            for (index, (value, target)) in from.iter().zip(to).enumerate() {
+                with_token(path, &index.to_string(), |path| diff(value, target, path, operations));
+            }
+            // from the back, so every index still points at the element it was computed for
+            for index in (to.len()..from.len()).rev() {
+                with_token(path, &index.to_string(), |path| operations.push(operation("remove", path, None)));
+            }
+            for (index, value) in to.iter().enumerate().skip(from.len()) {
+                with_token(path, &index.to_string(), |path| operations.push(operation("add", path, Some(value))));
+            }
+        }
+        (from, to) if from == to => {}
+        (_, to) => operations.push(operation("replace", path, Some(to))),
+    }
+}
+
+fn operation(op: &str, path: &str, value: Option<&Value>) -> Value {
+    let mut map = Map::new();
+    map.insert(String::from("op"), Value::String(String::from(op)));
+    map.insert(String::from("path"), Value::String(String::from(path)));
+    if let Some(value) = value {
+        map.insert(String::from("value"), value.clone());
+    }
+
+    Value::Object(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+
+    /// Applies the operations `diff` produces, to check a patch against its target
+    fn apply(value: &mut Value, patch: &Value) {
+        for operation in patch.as_array().unwrap() {
+            let path = operation["path"].as_str().unwrap();
+            let Some((parent, token)) = path.rsplit_once('/') else {
+                *value = operation["value"].clone();
+                continue;
+            };
+            let token = token.replace("~1", "/").replace("~0", "~");
+
+            match (operation["op"].as_str().unwrap(), value.pointer_mut(parent).unwrap()) {
+                ("remove", Value::Object(map)) => drop(map.remove(&token)),
+                ("remove", Value::Array(array)) => drop(array.remove(token.parse().unwrap())),
+                ("add", Value::Array(array)) => array.insert(token.parse().unwrap(), operation["value"].clone()),
+                (_, Value::Object(map)) => drop(map.insert(token, operation["value"].clone())),
+                (_, target) => *target.pointer_mut(&format!("/{token}")).unwrap() = operation["value"].clone(),
+            }
+        }
+    }
+
+    fn check(from: Value, to: Value, expected: Value) {
+        let patch = from.diff(&to);
+        assert_eq!(patch, expected);
+
+        let mut patched = from;
+        apply(&mut patched, &patch);
+        assert_eq!(patched, to);
+    }
+
+    #[test]
+    fn equal_values() {
+        check(json!({"a": [1, {"b": null}]}), json!({"a": [1, {"b": null}]}), json!([]));
+        check(json!(1), json!(1.0), json!([]));
+    }
+
+    #[test]
+    fn replaces_root() {
+        check(json!([1]), json!({"a": 1}), json!([{"op": "replace", "path": "", "value": {"a": 1}}]));
+        check(json!("a"), json!(null), json!([{"op": "replace", "path": "", "value": null}]));
+    }
+
+    #[test]
+    fn object_changes() {
+        check(
+            json!({"a": 1, "b": {"c": true, "d": "x"}, "e/~": 2}),
+            json!({"b": {"c": false, "d": "x"}, "e/~": 2, "f": [1]}),
+            json!([
+                {"op": "remove", "path": "/a"},
+                {"op": "replace", "path": "/b/c", "value": false},
+                {"op": "add", "path": "/f", "value": [1]},
+            ]),
+        );
+        check(json!({"a~b/c": 1}), json!({"a~b/c": 2}), json!([{"op": "replace", "path": "/a~0b~1c", "value": 2}]));
+    }
+
+    #[test]
+    fn array_changes() {
+        check(
+            json!([1, 2, 3, 4]),
+            json!([1, 5]),
+            json!([
+                {"op": "replace", "path": "/1", "value": 5},
+                {"op": "remove", "path": "/3"},
+                {"op": "remove", "path": "/2"},
+            ]),
+        );
+        check(
+            json!([{"a": 1}]),
+            json!([{"a": 2}, "x", "y"]),
+            json!([
+                {"op": "replace", "path": "/0/a", "value": 2},
+                {"op": "add", "path": "/1", "value": "x"},
+                {"op": "add", "path": "/2", "value": "y"},
+            ]),
+        );
+    }
+}
@@ -1,43 +1,242 @@
-use std::collections::HashMap;
-use crate::tokenize::{tokenize, TokenizeError};
-use crate::parse::{parse_tokens, TokenParseError};
+use std::error::Error;
+use std::{fmt, io};
+use std::str::FromStr;
 
+use crate::parse::{document_tokens, parse_tokens, TokenStream};
+use crate::tokenize::Cursor;
+
+#[macro_use]
+mod macros;
+
+mod access;
+mod borrowed;
+mod compare;
+mod convert;
+mod diff;
+mod location;
+pub mod map;
+mod merge;
+mod number;
+mod options;
 mod tokenize;
 mod parse;
+mod read;
+mod recover;
+mod schema;
+mod serialize;
+mod shared;
+mod stats;
+mod stream;
+mod visit;
+mod walk;
+#[cfg(feature = "serde")]
+mod serde_impl;
+
+pub use crate::access::Path;
+pub use crate::borrowed::{parse_borrowed, parse_borrowed_with, ValueRef};
+pub use crate::location::{Location, Span};
+pub use crate::map::{Entry, Map};
+pub use crate::merge::ArrayMerge;
+pub use crate::number::Number;
+pub use crate::options::{DuplicateKeyPolicy, NumberParser, ParseOptions, SerializeOptions};
+pub use crate::parse::TokenParseError;
+pub use crate::read::{from_reader, parse_file, parse_ndjson};
+pub use crate::recover::{parse_collect_errors, parse_collect_errors_with};
+pub use crate::schema::{validate_schema, SchemaError};
+pub use crate::serialize::Indent;
+pub use crate::shared::{parse_shared, parse_shared_with, SharedStr, SharedValue};
+pub use crate::stats::{document_stats, Stats};
+pub use crate::stream::StreamParser;
+pub use crate::tokenize::{tokenize, SpannedToken, Token, TokenizeError, Tokenizer};
+pub use crate::visit::{parse_with_visitor, Visitor};
+pub use crate::walk::PathSegment;
 
 pub fn parse(input: String) -> Result<Value, ParseError> {
-    let tokens = tokenize(input)?;
-    let value = parse_tokens(&mut tokens.into_iter().peekable())?;
+    parse_with(input, &ParseOptions::default())
+}
+
+pub fn parse_with(input: String, options: &ParseOptions) -> Result<Value, ParseError> {
+    parse_tokenizer(Tokenizer::new(&input).with_options(options), options)
+}
+
+/// Parses a single value, pulling tokens from the tokenizer as they are needed
+fn parse_tokenizer<C: Cursor>(tokenizer: Tokenizer<C>, options: &ParseOptions) -> Result<Value, ParseError> {
+    let mut tokens = document_tokens(tokenizer)?;
+    let value = parse_tokens(&mut tokens, options)?;
+
+    if tokens.next()?.is_some() {
+        return Err(ParseError::TrailingTokens);
+    }
+
     Ok(value)
 }
 
-#[derive(Debug, PartialEq)]
+/// Parses consecutive top-level values separated by whitespace, like `{"a":1} {"b":2} 3`
+pub fn parse_many(input: &str) -> Result<Vec<Value>, ParseError> {
+    let options = ParseOptions::default();
+    let mut tokens = TokenStream::new(Tokenizer::new(input));
+    let mut values = Vec::new();
+
+    while tokens.peek()?.is_some() {
+        values.push(parse_tokens(&mut tokens, &options)?);
+    }
+
+    Ok(values)
+}
+
+/// Parses the value at the start of input and returns it with the number of bytes it takes up,
+/// whatever follows the value is left unread
+pub fn parse_prefix(input: &str) -> Result<(Value, usize), ParseError> {
+    let mut tokens = document_tokens(Tokenizer::new(input))?;
+    let value = parse_tokens(&mut tokens, &ParseOptions::default())?;
+
+    Ok((value, tokens.end().byte_offset))
+}
+
+/// Checks that input is a single well-formed JSON value without building a [`Value`] tree
+pub fn validate(input: &str) -> Result<(), ParseError> {
+    parse_with_visitor(input, &ParseOptions::default(), &mut ())
+}
+
+pub fn parse_bytes(input: &[u8]) -> Result<Value, ParseError> {
+    let input = std::str::from_utf8(input)
+        .map_err(|err| ParseError::InvalidUtf8(err.valid_up_to()))?;
+
+    parse(String::from(input))
+}
+
+/// JSON value, equality and `Hash` follow [`Number`] and [`Map`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Value {
     Null,
     Boolean(bool),
     String(String),
-    Number(f64),
+    Number(Number),
+    /// Number literal kept exactly as written, see [`ParseOptions::preserve_number_text`]
+    RawNumber(String),
     Array(Vec<Value>),
-    Object(HashMap<String,Value>)
+    Object(Map)
+}
+
+impl FromStr for Value {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse_tokenizer(Tokenizer::new(input), &ParseOptions::default())
+    }
 }
 
 #[cfg(test)]
 impl Value {
     pub(crate) fn object<const N: usize>(pairs: [(&'static str, Self); N]) -> Self {
         let owned_pairs = pairs.map(|(key, value)| (String::from(key), value));
-        let map = HashMap::from(owned_pairs);
+        let map = Map::from(owned_pairs);
         Self::Object(map)
     }
 
     pub(crate) fn string(s: &str) -> Self {
         Self::String(String::from(s))
     }
+
+    pub(crate) fn number(n: impl Into<Number>) -> Self {
+        Self::Number(n.into())
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub enum ParseError {
     TokenizeError(TokenizeError),
     ParseError(TokenParseError),
+    /// Input has no characters at all
+    EmptyInput,
+    /// Input holds only whitespace, and comments when they are allowed
+    BlankInput,
+    /// Input continues after the first complete value
+    TrailingTokens,
+    /// Input is not valid UTF-8, holds byte offset where decoding failed
+    InvalidUtf8(usize),
+    /// Reading the input failed
+    Io(io::Error),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TokenizeError(err) => err.fmt(f),
+            Self::ParseError(err) => err.fmt(f),
+            Self::EmptyInput => f.write_str("input is empty"),
+            Self::BlankInput => f.write_str("input holds no JSON value"),
+            Self::TrailingTokens => f.write_str("unexpected content after the JSON value"),
+            Self::InvalidUtf8(offset) => write!(f, "invalid UTF-8 at byte offset {offset}"),
+            Self::Io(err) => write!(f, "failed to read input: {err}"),
+        }
+    }
+}
+
+impl ParseError {
+    /// Where in the source the error was found, if it points at a single place
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Self::TokenizeError(err) => Some(err.location()),
+            Self::ParseError(err) => err.location(),
+            Self::EmptyInput | Self::BlankInput | Self::TrailingTokens | Self::InvalidUtf8(_) | Self::Io(_) => None,
+        }
+    }
+
+    /// Error for input without a single token, `end` is where the input ends
+    pub(crate) fn no_value(end: Location) -> Self {
+        if end.byte_offset == 0 { Self::EmptyInput } else { Self::BlankInput }
+    }
+
+    /// Renders the error message followed by the annotated line of `source` it points at
+    ///
+    /// Input that ends too early is annotated right after its last character that isn't whitespace.
+    pub fn render(&self, source: &str) -> String {
+        let location = match self {
+            Self::ParseError(TokenParseError::UnexpectedEof) => {
+                Some(source.trim_end().chars().fold(Location::start(), Location::advance))
+            }
+            _ => self.location(),
+        };
+
+        match location {
+            Some(location) => format!("{self}\n{}", location.annotate(source)),
+            None => self.to_string(),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::TokenizeError(err) => Some(err),
+            Self::ParseError(err) => Some(err),
+            Self::Io(err) => Some(err),
+            Self::EmptyInput | Self::BlankInput | Self::TrailingTokens | Self::InvalidUtf8(_) => None,
+        }
+    }
+}
+
+/// I/O errors are compared by their kind only
+impl PartialEq for ParseError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::TokenizeError(a), Self::TokenizeError(b)) => a == b,
+            (Self::ParseError(a), Self::ParseError(b)) => a == b,
+            (Self::EmptyInput, Self::EmptyInput) => true,
+            (Self::BlankInput, Self::BlankInput) => true,
+            (Self::TrailingTokens, Self::TrailingTokens) => true,
+            (Self::InvalidUtf8(a), Self::InvalidUtf8(b)) => a == b,
+            (Self::Io(a), Self::Io(b)) => a.kind() == b.kind(),
+            _ => false,
+        }
+    }
+}
+
+impl From<io::Error> for ParseError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
 }
 
 impl From<TokenParseError> for ParseError {
@@ -54,8 +253,13 @@ impl From<TokenizeError> for ParseError {
 
 #[cfg(test)]
 mod tests {
-    use crate::{ParseError, parse};
-    use crate::Value;
+    use std::collections::HashSet;
+    use std::error::Error;
+
+    use crate::{DuplicateKeyPolicy, ParseError, ParseOptions, parse, parse_bytes, parse_many, parse_prefix, parse_with, validate};
+    use crate::{Location, Value};
+    use crate::tokenize::{Token, TokenizeError};
+    use crate::parse::TokenParseError;
 
     fn check_valid(input: &str, expected: Value) {
         let actual = parse(String::from(input)).unwrap();
@@ -84,6 +288,244 @@ mod tests {
         check_valid("false", Value::Boolean(false));
     }
 
+    #[test]
+    fn large_object() {
+        // would take minutes if every key were looked up by scanning the ones before it
+        let keys = 100_000;
+        let entries: Vec<String> = (0..keys).map(|i| format!("\"k{i}\": {i}")).collect();
+        let input = format!("{{{}}}", entries.join(", "));
+
+        let value = parse(input).unwrap();
+
+        assert_eq!(value.len(), Some(keys));
+        assert_eq!(value["k99999"], Value::number(99999));
+    }
+
+    #[test]
+    fn empty_input() {
+        check_error("", ParseError::EmptyInput);
+        assert_eq!(validate(""), Err(ParseError::EmptyInput));
+        assert_eq!(parse_prefix(""), Err(ParseError::EmptyInput));
+        assert_eq!(parse_bytes(b""), Err(ParseError::EmptyInput));
+    }
+
+    #[test]
+    fn whitespace_only_input() {
+        let options = ParseOptions { allow_comments: true, ..ParseOptions::default() };
+
+        check_error("   ", ParseError::BlankInput);
+        check_error("\u{feff}", ParseError::BlankInput);
+        assert_eq!(parse_with(String::from(" // nothing\n/* here */"), &options), Err(ParseError::BlankInput));
+        assert_eq!(validate("\n"), Err(ParseError::BlankInput));
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        check_valid("1 ", Value::number(1));
+        check_valid("[true]\n\t\r\n", Value::Array(vec![Value::Boolean(true)]));
+        check_error("1 \n 2 ", ParseError::TrailingTokens);
+        assert_eq!(parse_many("1 2\n"), Ok(vec![Value::number(1), Value::number(2)]));
+    }
+
+    #[test]
+    fn trailing_comment() {
+        let options = ParseOptions { allow_comments: true, ..ParseOptions::default() };
+
+        assert_eq!(parse_with(String::from("null // done\n  "), &options), Ok(Value::Null));
+    }
+
+    #[test]
+    fn trailing_value() {
+        check_error("1 2", ParseError::TrailingTokens);
+        check_error("true null", ParseError::TrailingTokens);
+    }
+
+    #[test]
+    fn trailing_punctuation() {
+        check_error(r#"{"key": null}]"#, ParseError::TrailingTokens);
+    }
+
+    #[test]
+    fn unexpected_punctuation() {
+        check_error(r#"{"a": }"#, TokenParseError::UnexpectedToken { token: Token::RightBrace, location: Location::new(1, 7, 6) });
+    }
+
+    #[test]
+    fn unbalanced_closer_at_top_level() {
+        let messages = [
+            ("}", "unexpected closing brace `}` at top level, line 1, column 1"),
+            (" ]", "unexpected closing bracket `]` at top level, line 1, column 2"),
+            (",", "unexpected comma `,` at top level, line 1, column 1"),
+            (":", "unexpected colon `:` at top level, line 1, column 1"),
+        ];
+
+        for (input, message) in messages {
+            assert_eq!(parse(String::from(input)).unwrap_err().to_string(), message);
+        }
+        check_error("]", TokenParseError::UnexpectedAtTopLevel { token: Token::RightBracket, location: Location::new(1, 1, 0) });
+        assert_eq!(
+            parse(String::from("[}")).unwrap_err().to_string(),
+            "unexpected closing brace `}` at line 1, column 2",
+        );
+    }
+
+    #[test]
+    fn missing_comma() {
+        check_error("[1 2]", TokenParseError::ExpectedComma { found: Token::number(2), location: Location::new(1, 4, 3) });
+        check_error(r#"{"a": 1 "b": 2}"#, TokenParseError::ExpectedComma { found: Token::string("b"), location: Location::new(1, 9, 8) });
+        check_error("[1", TokenParseError::UnexpectedEof);
+
+        assert_eq!(parse(String::from("[1 true]")).unwrap_err().to_string(), "expected `,` but found `true` at line 1, column 4");
+        assert_eq!(parse(String::from("[1 2]")).unwrap_err().to_string(), "expected `,` but found number `2` at line 1, column 4");
+        assert_eq!(
+            parse(String::from(r#"{"x": 1 "y": 2}"#)).unwrap_err().to_string(),
+            "expected `,` but found string \"y\" at line 1, column 9",
+        );
+    }
+
+    #[test]
+    fn error_locations() {
+        let err = parse(String::from("[1, ?]")).unwrap_err();
+        assert_eq!(err.location(), Some(Location::new(1, 5, 4)));
+
+        let err = parse(String::from("{\"a\" 1}")).unwrap_err();
+        assert_eq!(err.location(), Some(Location::new(1, 6, 5)));
+
+        assert_eq!(parse(String::from("[1")).unwrap_err().location(), None);
+        assert_eq!(parse(String::from("1 2")).unwrap_err().location(), None);
+    }
+
+    #[test]
+    fn render_error() {
+        let input = "{\n  \"a\" 1}";
+
+        let actual = parse(String::from(input)).unwrap_err().render(input);
+
+        assert_eq!(actual, "expected `:` but found number `1` at line 2, column 7\n2 |   \"a\" 1}\n  |       ^");
+        assert_eq!(parse(String::from("[1, \n\n")).unwrap_err().render("[1, \n\n"), "unexpected end of input\n1 | [1, \n  |    ^");
+        assert_eq!(parse(String::from("1 2")).unwrap_err().render("1 2"), "unexpected content after the JSON value");
+    }
+
+    #[test]
+    fn unclosed_object() {
+        check_error(r#"{"a":1"#, TokenParseError::UnexpectedEof);
+    }
+
+    #[test]
+    fn missing_colon_location() {
+        check_error(
+            "{\n  \"a\" 1}",
+            TokenParseError::ExpectedColon { found: Token::number(1), location: Location::new(2, 7, 8) },
+        );
+    }
+
+    #[test]
+    fn nesting_deeper_than_max_depth() {
+        let options = ParseOptions { max_depth: 3, ..ParseOptions::default() };
+
+        assert!(parse_with(String::from("[[[1]]]"), &options).is_ok());
+        assert_eq!(
+            parse_with(String::from(r#"[[{"a": [1]}]]"#), &options).unwrap_err(),
+            TokenParseError::DepthLimitExceeded { location: Location::new(1, 9, 8) }.into(),
+        );
+    }
+
+    #[test]
+    fn too_many_elements() {
+        let options = ParseOptions { max_elements: Some(3), ..ParseOptions::default() };
+
+        assert!(parse_with(String::from("[[1, 2, 3], [4]]"), &options).is_ok());
+        assert_eq!(
+            parse_with(String::from("[1, 2, 3, 4]"), &options).unwrap_err(),
+            TokenParseError::TooManyElements { location: Location::new(1, 11, 10) }.into(),
+        );
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let input = r#"{"a": 1, "b": 2, "a": 3}"#;
+        let policy = |duplicate_keys| ParseOptions { duplicate_keys, ..ParseOptions::default() };
+
+        assert_eq!(parse(String::from(input)).unwrap(), Value::object([("a", Value::number(3)), ("b", Value::number(2))]));
+        assert_eq!(
+            parse_with(String::from(input), &policy(DuplicateKeyPolicy::FirstWins)).unwrap(),
+            Value::object([("a", Value::number(1)), ("b", Value::number(2))]),
+        );
+        assert_eq!(
+            parse_with(String::from(input), &policy(DuplicateKeyPolicy::Error)).unwrap_err(),
+            TokenParseError::DuplicateKey { key: String::from("a"), location: Location::new(1, 18, 17) }.into(),
+        );
+        assert!(parse_with(String::from(r#"{"a": {"a": 1}, "b": [{"a": 2}]}"#), &policy(DuplicateKeyPolicy::Error)).is_ok());
+    }
+
+    #[test]
+    fn trailing_commas() {
+        let input = r#"{"tags": ["a", "b",],}"#;
+        let options = ParseOptions { allow_trailing_commas: true, ..ParseOptions::default() };
+        let expected = Value::object([("tags", Value::Array(vec![Value::string("a"), Value::string("b")]))]);
+
+        assert_eq!(parse_with(String::from(input), &options).unwrap(), expected);
+        check_error(input, TokenParseError::TrailingComma { location: Location::new(1, 19, 18) });
+    }
+
+    #[test]
+    fn comments() {
+        let input = "{\n  // user name\n  \"name\": /* inline */ \"x\"\n}";
+        let options = ParseOptions { allow_comments: true, ..ParseOptions::default() };
+
+        assert_eq!(parse_with(String::from(input), &options).unwrap(), Value::object([("name", Value::string("x"))]));
+        check_error(input, TokenizeError::CharNotRecognized { ch: '/', location: Location::new(2, 3, 4) });
+    }
+
+    #[test]
+    fn unquoted_keys() {
+        let input = "{name: \"x\", nested: {ok: true}}";
+        let options = ParseOptions { allow_unquoted_keys: true, ..ParseOptions::default() };
+        let expected = Value::object([
+            ("name", Value::string("x")),
+            ("nested", Value::object([("ok", Value::Boolean(true))])),
+        ]);
+
+        assert_eq!(parse_with(String::from(input), &options).unwrap(), expected);
+        check_error(input, TokenizeError::UnfinishedLiteralValue { location: Location::new(1, 3, 2) });
+    }
+
+    #[test]
+    fn single_quotes() {
+        let input = "{'name': 'x', \"list\": ['a', \"b\"]}";
+        let options = ParseOptions { allow_single_quotes: true, ..ParseOptions::default() };
+        let expected = Value::object([
+            ("name", Value::string("x")),
+            ("list", Value::Array(vec![Value::string("a"), Value::string("b")])),
+        ]);
+
+        assert_eq!(parse_with(String::from(input), &options).unwrap(), expected);
+        check_error(input, TokenizeError::CharNotRecognized { ch: '\'', location: Location::new(1, 2, 1) });
+    }
+
+    #[test]
+    fn unclosed_string() {
+        check_error(r#""string"#, TokenizeError::UnclosedQuotes { location: Location::new(1, 1, 0) });
+    }
+
+    #[test]
+    fn trailing_backslash_in_string() {
+        check_error(r#"{"a": "abc\"}"#, TokenizeError::UnclosedQuotes { location: Location::new(1, 7, 6) });
+        assert_eq!(parse(String::from(r#"{"a": "abc\\"}"#)), Ok(Value::object([("a", Value::string("abc\\"))])));
+    }
+
+    #[test]
+    fn control_character_in_key() {
+        let input = "{\"a\nb\": 1}";
+        let expected = TokenizeError::InvalidControlCharacter { ch: '\n', location: Location::new(1, 4, 3) };
+        let options = ParseOptions { allow_single_quotes: true, ..ParseOptions::default() };
+
+        check_error(input, expected.clone());
+        assert_eq!(parse_with(input.replace('"', "'"), &options), Err(expected.clone().into()));
+        assert_eq!(crate::parse_borrowed(input), Err(expected.into()));
+        check_valid(r#"{"a\nb": 1}"#, Value::object([("a\nb", Value::number(1))]));
+    }
+
     #[test]
     fn array_with_null() {
         check_valid("[null]", Value::Array(vec![Value::Null]))
@@ -102,9 +544,9 @@ mod tests {
         check_valid(
             "[1, 2, 3]",
             Value::Array(vec![
-                Value::Number(1.0),
-                Value::Number(2.0),
-                Value::Number(3.0),
+                Value::number(1.0),
+                Value::number(2.0),
+                Value::number(3.0),
             ]),
         )
     }
@@ -126,7 +568,7 @@ mod tests {
     fn object_with_number() {
         check_valid(
             r#"{"key": 1}"#,
-            Value::object([("key", Value::Number(1.0))]),
+            Value::object([("key", Value::number(1.0))]),
         );
     }
 
@@ -159,6 +601,125 @@ mod tests {
         )
     }
 
+    #[test]
+    fn object_keeps_key_order() {
+        let value = parse(String::from(r#"{"b": 1, "c": 2, "a": 3}"#)).unwrap();
+
+        assert_eq!(value.to_string(), r#"{"b":1,"c":2,"a":3}"#);
+    }
+
+    #[test]
+    fn object_with_escaped_chars() {
+        check_valid(
+            r#"{"key \"quoted\"": "value with \"quotes\" and \n newline"}"#,
+            Value::object([("key \"quoted\"", Value::string("value with \"quotes\" and \n newline"))]),
+        );
+    }
+
+    #[test]
+    fn invalid_escape() {
+        check_error(r#"["\q"]"#, TokenizeError::InvalidEscape { ch: 'q', location: Location::new(1, 3, 2) });
+    }
+
+    #[test]
+    fn raw_numbers_round_trip() {
+        let input = r#"{"price":1.10,"id":123456789012345678901234567890,"big":1e400}"#;
+        let options = ParseOptions { preserve_number_text: true, ..ParseOptions::default() };
+
+        let value = parse_with(String::from(input), &options).unwrap();
+
+        assert_eq!(value["price"], Value::RawNumber(String::from("1.10")));
+        assert_eq!(value.to_string(), input);
+    }
+
+    /// Keeps decimals exact as strings, like a number parser for money would
+    fn parse_decimal(text: &str) -> Result<Value, String> {
+        if text.contains(['e', 'E']) {
+            return Err(String::from("exponents are not supported"));
+        }
+
+        Ok(Value::String(String::from(text)))
+    }
+
+    #[test]
+    fn number_parser() {
+        let options = ParseOptions { number_parser: Some(parse_decimal), ..ParseOptions::default() };
+
+        let actual = parse_with(String::from(r#"{"price": 0.10, "items": [3]}"#), &options).unwrap();
+
+        assert_eq!(actual, Value::object([("price", Value::string("0.10")), ("items", Value::Array(vec![Value::string("3")]))]));
+        assert_eq!(
+            parse_with(String::from("[1, 2e3]"), &options).unwrap_err(),
+            TokenParseError::NumberParserFailed {
+                message: String::from("exponents are not supported"),
+                location: Location::new(1, 5, 4),
+            }.into(),
+        );
+    }
+
+    #[test]
+    fn number_parser_gets_valid_literals_only() {
+        fn no_call(text: &str) -> Result<Value, String> {
+            panic!("number parser called with {text:?}")
+        }
+        let options = ParseOptions { number_parser: Some(no_call), ..ParseOptions::default() };
+
+        for input in ["-", "[-]", "[-e5]", "[-.5]"] {
+            let actual = parse_with(String::from(input), &options).unwrap_err();
+
+            assert!(matches!(actual, ParseError::TokenizeError(TokenizeError::InvalidNumber { .. })), "{input}");
+        }
+    }
+
+    #[test]
+    fn values_in_hash_set() {
+        let values = ["[1, {\"a\": null, \"b\": NaN}]", "[1.0, {\"b\": NaN, \"a\": null}]", "[-0.0]", "[0]", "[0.5]"];
+        let options = ParseOptions { allow_nan_inf: true, ..ParseOptions::default() };
+
+        let set: HashSet<Value> = values
+            .into_iter()
+            .map(|input| parse_with(String::from(input), &options).unwrap())
+            .collect();
+
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn cloned_subtree_is_independent() {
+        let value = parse(String::from(r#"{"user": {"tags": ["admin"]}}"#)).unwrap();
+
+        let mut user = value["user"].clone();
+        user["tags"][0] = Value::string("guest");
+
+        assert_eq!(value["user"]["tags"][0], Value::string("admin"));
+        assert_eq!(user["tags"][0], Value::string("guest"));
+    }
+
+    #[test]
+    fn from_str() {
+        let value: Value = r#"{"key": [1]}"#.parse().unwrap();
+
+        assert_eq!(value, Value::object([("key", Value::Array(vec![Value::number(1)]))]));
+        assert_eq!("[1,".parse::<Value>().unwrap_err(), TokenParseError::UnexpectedEof.into());
+    }
+
+    #[test]
+    fn error_display() {
+        let err = parse(String::from("{\"a\" 1}")).unwrap_err();
+
+        assert_eq!(err.to_string(), "expected `:` but found number `1` at line 1, column 6");
+    }
+
+    #[test]
+    fn error_source_chain() {
+        let err: Box<dyn Error> = Box::new(parse(String::from("--1")).unwrap_err());
+
+        let tokenize_error = err.source().unwrap();
+
+        assert_eq!(tokenize_error.to_string(), "Invalid number provided. at line 1, column 1");
+        assert!(tokenize_error.source().is_none());
+    }
+
     #[test]
     fn parse_valid() {
         check_valid(
@@ -166,7 +727,7 @@ mod tests {
             Value::object([
                 ("name", Value::string("minecraft")),
                 ("is my life", Value::Boolean(true)),
-                ("version", Value::Number(1.5))])
+                ("version", Value::number(1.5))])
         );
     }
 
@@ -176,7 +737,7 @@ mod tests {
             r#"{"user": {"id": 1415436218769, "tags": ["admin", "ru"]}}"#,
             Value::object([
                 ("user", Value::object([
-                    ("id", Value::Number(1415436218769.0)),
+                    ("id", Value::number(1415436218769.0)),
                     ("tags", Value::Array(vec![
                         Value::string("admin"),
                         Value::string("ru")
@@ -186,4 +747,90 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn many_values() {
+        let actual = parse_many(r#"{"a":1} {"b":2}
+            3 [true]"#).unwrap();
+        let expected = vec![
+            Value::object([("a", Value::number(1))]),
+            Value::object([("b", Value::number(2))]),
+            Value::number(3),
+            Value::Array(vec![Value::Boolean(true)]),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn many_values_empty_input() {
+        assert_eq!(parse_many(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn many_values_stops_at_first_error() {
+        assert_eq!(parse_many(r#"1 {"a" 2}"#), Err(TokenParseError::ExpectedColon { found: Token::number(2), location: Location::new(1, 8, 7) }.into()));
+        assert_eq!(parse_many("[1] [2"), Err(TokenParseError::UnexpectedEof.into()));
+    }
+
+    #[test]
+    fn prefix_value() {
+        let input = "  {\"a\": [1, \"é\"]}\n\u{0}\u{1}binary";
+
+        let (value, consumed) = parse_prefix(input).unwrap();
+
+        assert_eq!(value, Value::object([("a", Value::Array(vec![Value::number(1), Value::string("é")]))]));
+        assert_eq!(&input[consumed..], "\n\u{0}\u{1}binary");
+    }
+
+    #[test]
+    fn prefix_scalars() {
+        assert_eq!(parse_prefix("123abc"), Ok((Value::number(123), 3)));
+        assert_eq!(parse_prefix("true false"), Ok((Value::Boolean(true), 4)));
+        assert_eq!(parse_prefix("[]]"), Ok((Value::Array(vec![]), 2)));
+    }
+
+    #[test]
+    fn prefix_incomplete_value() {
+        assert_eq!(parse_prefix("[1, 2"), Err(TokenParseError::UnexpectedEof.into()));
+    }
+
+    #[test]
+    fn validate_valid() {
+        assert_eq!(validate(r#"{"user": {"id": 1, "tags": ["admin", [], {}]}}"#), Ok(()));
+        assert_eq!(validate("null"), Ok(()));
+    }
+
+    #[test]
+    fn validate_reports_same_errors_as_parse() {
+        let inputs = ["", "[1,", r#"{"a" 1}"#, "[1 2]", "[1,]", "1 2", r#"["\q"]"#];
+
+        for input in inputs {
+            assert_eq!(validate(input), parse(String::from(input)).map(drop), "{input}");
+        }
+    }
+
+    #[test]
+    fn bytes_valid() {
+        let actual = parse_bytes(br#"{"key": [true]}"#).unwrap();
+        let expected = Value::object([("key", Value::Array(vec![Value::Boolean(true)]))]);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn bytes_with_bom() {
+        let actual = parse_bytes(b"\xef\xbb\xbf{\"a\": 1}").unwrap();
+
+        assert_eq!(actual, Value::object([("a", Value::number(1))]));
+        assert_eq!(parse_prefix("\u{feff}null rest"), Ok((Value::Null, 7)));
+    }
+
+    #[test]
+    fn bytes_invalid_utf8() {
+        let actual = parse_bytes(b"[\"ab\xff\"]").unwrap_err();
+        let expected = ParseError::InvalidUtf8(4);
+
+        assert_eq!(actual, expected);
+    }
 }
@@ -0,0 +1,121 @@
+use std::fmt;
+
+/// Position of a character in the source text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    /// Line number, starting from 1
+    pub line: usize,
+    /// Column number in characters, starting from 1
+    pub column: usize,
+    /// Offset in bytes from the start of the input
+    pub byte_offset: usize,
+}
+
+impl Location {
+    pub(crate) fn start() -> Self {
+        Self { line: 1, column: 1, byte_offset: 0 }
+    }
+
+    /// Location right after the given character
+    pub(crate) fn advance(self, ch: char) -> Self {
+        let byte_offset = self.byte_offset + ch.len_utf8();
+
+        if ch == '\n' {
+            Self { line: self.line + 1, column: 1, byte_offset }
+        } else {
+            Self { line: self.line, column: self.column + 1, byte_offset }
+        }
+    }
+
+    /// Renders the line of the source this location is on, with a caret under its column
+    ///
+    /// ```text
+    /// 2 |   "a" 1}
+    ///   |       ^
+    /// ```
+    pub fn annotate(&self, source: &str) -> String {
+        let line = source.split('\n').nth(self.line.saturating_sub(1)).unwrap_or_default().trim_end_matches('\r');
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        // tabs are kept so the caret lines up however wide they are displayed
+        let indent: String = line
+            .chars()
+            .chain(std::iter::repeat(' '))
+            .take(self.column.saturating_sub(1))
+            .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        format!("{number} | {line}\n{gutter} | {indent}^")
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Range of the source text between two locations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Location of the first character
+    pub start: Location,
+    /// Location right after the last character
+    pub end: Location,
+}
+
+#[cfg(test)]
+impl Location {
+    pub(crate) fn new(line: usize, column: usize, byte_offset: usize) -> Self {
+        Self { line, column, byte_offset }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Location;
+
+    #[test]
+    fn advances_column() {
+        let actual = Location::start().advance('a');
+
+        assert_eq!(actual, Location::new(1, 2, 1))
+    }
+
+    #[test]
+    fn advances_line() {
+        let actual = Location::start().advance('a').advance('\n');
+
+        assert_eq!(actual, Location::new(2, 1, 2))
+    }
+
+    #[test]
+    fn annotates_location() {
+        let source = "{\n  \"a\" 1}";
+
+        let actual = Location::new(2, 7, 8).annotate(source);
+
+        assert_eq!(actual, "2 |   \"a\" 1}\n  |       ^");
+    }
+
+    #[test]
+    fn annotates_after_tabs_and_past_line_end() {
+        assert_eq!(Location::new(1, 3, 2).annotate("\t\tx"), "1 | \t\tx\n  | \t\t^");
+        assert_eq!(Location::new(1, 3, 2).annotate("[1"), "1 | [1\n  |   ^");
+        assert_eq!(Location::new(2, 1, 3).annotate("[1\n"), "2 | \n  | ^");
+    }
+
+    #[test]
+    fn annotates_zero_line_and_column() {
+        let actual = Location { line: 0, column: 0, byte_offset: 0 }.annotate("[1");
+
+        assert_eq!(actual, "0 | [1\n  | ^");
+    }
+
+    #[test]
+    fn advances_multi_byte_char() {
+        let actual = Location::start().advance('💩');
+
+        assert_eq!(actual, Location::new(1, 2, 4))
+    }
+}
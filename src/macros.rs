@@ -0,0 +1,113 @@
+/// Builds a [`Value`](crate::Value) from JSON-like syntax
+///
+/// Keys must be string literals, values are `null`, nested arrays and objects,
+/// or any expression that converts into a `Value`.
+///
+/// ```
+/// use json_parser::json;
+///
+/// let value = json!({"name": "x", "tags": ["a", 1.5], "ok": true, "missing": null});
+///
+/// assert_eq!(value["tags"][1], 1.5);
+/// ```
+#[macro_export]
+macro_rules! json {
+    (null) => {
+        $crate::Value::Null
+    };
+    ([ $($tokens:tt)* ]) => {
+        $crate::Value::Array($crate::json!(@array [] () $($tokens)*))
+    };
+    ({}) => {
+        $crate::Value::Object($crate::Map::new())
+    };
+    ({ $($tokens:tt)+ }) => {{
+        let mut map = $crate::Map::new();
+        $crate::json!(@object map $($tokens)*);
+        $crate::Value::Object(map)
+    }};
+    ($other:expr) => {
+        $crate::Value::from($other)
+    };
+
+    // array elements are collected token by token up to the next comma
+    (@array [$($done:expr),*] ()) => {
+        ::std::vec![$($done),*]
+    };
+    (@array [$($done:expr),*] ($($element:tt)+)) => {
+        $crate::json!(@array [$($done,)* $crate::json!($($element)+)] ())
+    };
+    (@array [$($done:expr),*] ($($element:tt)+) , $($rest:tt)*) => {
+        $crate::json!(@array [$($done,)* $crate::json!($($element)+)] () $($rest)*)
+    };
+    (@array [$($done:expr),*] ($($element:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::json!(@array [$($done),*] ($($element)* $next) $($rest)*)
+    };
+
+    // object values are collected the same way, each entry is inserted once complete
+    (@object $map:ident) => {};
+    (@object $map:ident $key:tt : $($rest:tt)*) => {
+        $crate::json!(@entry $map $key () $($rest)*)
+    };
+    (@entry $map:ident $key:tt ($($value:tt)+)) => {
+        $map.insert(::std::string::String::from($key), $crate::json!($($value)+));
+    };
+    (@entry $map:ident $key:tt ($($value:tt)+) , $($rest:tt)*) => {
+        $map.insert(::std::string::String::from($key), $crate::json!($($value)+));
+        $crate::json!(@object $map $($rest)*);
+    };
+    (@entry $map:ident $key:tt ($($value:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::json!(@entry $map $key ($($value)* $next) $($rest)*)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, Value};
+
+    #[test]
+    fn literals() {
+        assert_eq!(json!(null), Value::Null);
+        assert_eq!(json!(true), Value::Boolean(true));
+        assert_eq!(json!(-1.5), Value::number(-1.5));
+        assert_eq!(json!("a"), Value::string("a"));
+    }
+
+    #[test]
+    fn empty_containers() {
+        assert_eq!(json!([]), Value::Array(vec![]));
+        assert_eq!(json!({}), Value::object([]));
+    }
+
+    #[test]
+    fn nested_document() {
+        let expected = parse(String::from(
+            r#"{"name": "x", "tags": ["a", 1.5, -2, [null]], "ok": true, "missing": null, "inner": {"a": {}}}"#,
+        ))
+        .unwrap();
+
+        let actual = json!({
+            "name": "x",
+            "tags": ["a", 1.5, -2, [null]],
+            "ok": true,
+            "missing": null,
+            "inner": {"a": {}},
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn expressions() {
+        let name = String::from("x");
+        let count = 2 + 3;
+
+        let actual = json!({"name": name, "count": count, "list": [count * 2, "a".repeat(2)]});
+
+        assert_eq!(actual, Value::object([
+            ("name", Value::string("x")),
+            ("count", Value::number(5)),
+            ("list", Value::Array(vec![Value::number(10), Value::string("aa")])),
+        ]));
+    }
+}
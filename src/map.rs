@@ -0,0 +1,400 @@
+//! The [`Map`] objects are stored in and its iterators
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::fmt;
+use std::{slice, vec};
+
+use crate::Value;
+
+/// Object map that keeps keys in their insertion order
+///
+/// Lookups go through a hash index of the entries, so they take constant time and parsing an
+/// object stays linear in its number of keys.
+#[derive(Default, Clone)]
+pub struct Map {
+    entries: Vec<(String, Value)>,
+    /// Position of every key in `entries`
+    index: HashMap<String, usize>,
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter { entries: self.entries.iter() }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut { entries: self.entries.iter_mut() }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Value> {
+        self.iter_mut().map(|(_, value)| value)
+    }
+
+    /// Handle to the value of a key for updating it in place, whether the key is present or not
+    pub fn entry(&mut self, key: String) -> Entry<'_> {
+        Entry { map: self, key }
+    }
+}
+
+/// Key of a [`Map`] that may or may not be present, see [`Map::entry`]
+pub struct Entry<'a> {
+    map: &'a mut Map,
+    key: String,
+}
+
+impl<'a> Entry<'a> {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Value of the key, inserting `default` first when the key is missing
+    pub fn or_insert(self, default: Value) -> &'a mut Value {
+        self.or_insert_with(|| default)
+    }
+
+    /// Value of the key, inserting the value `f` returns first when the key is missing
+    pub fn or_insert_with(self, f: impl FnOnce() -> Value) -> &'a mut Value {
+        if !self.map.contains_key(&self.key) {
+            self.map.insert(self.key.clone(), f());
+        }
+
+        self.map.get_mut(&self.key).expect("the key was just inserted")
+    }
+
+    /// Runs `f` on the value when the key is present
+    pub fn and_modify(self, f: impl FnOnce(&mut Value)) -> Self {
+        if let Some(value) = self.map.get_mut(&self.key) {
+            f(value);
+        }
+
+        self
+    }
+}
+
+impl Map {
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.position(key).is_some()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.position(key).map(|i| &self.entries[i].1)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.position(key).map(|i| &mut self.entries[i].1)
+    }
+
+    /// Inserts a key/value pair, a key that is already present keeps its position
+    /// and the previous value is returned
+    pub fn insert(&mut self, key: String, value: Value) -> Option<Value> {
+        match self.position(&key) {
+            Some(i) => Some(std::mem::replace(&mut self.entries[i].1, value)),
+            None => {
+                self.index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Removes a key, preserving the order of the remaining entries
+    ///
+    /// Takes time linear in the number of entries after it, which move up by one.
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        let i = self.index.remove(key)?;
+        for position in self.index.values_mut() {
+            if *position > i {
+                *position -= 1;
+            }
+        }
+
+        Some(self.entries.remove(i).1)
+    }
+
+    /// Orders the entries by key, comparing keys byte by byte which matches code point order
+    pub fn sort_keys(&mut self) {
+        self.entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (i, (key, _)) in self.entries.iter().enumerate() {
+            self.index.insert(key.clone(), i);
+        }
+    }
+
+    fn position(&self, key: &str) -> Option<usize> {
+        self.index.get(key).copied()
+    }
+}
+
+/// Maps are equal when they hold the same entries, regardless of order
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self.iter().all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl Eq for Map {}
+
+/// Entries are hashed independently and combined with a commutative sum, so order doesn't matter
+impl Hash for Map {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let sum = self.iter().fold(0u64, |sum, entry| {
+            let mut hasher = DefaultHasher::new();
+            entry.hash(&mut hasher);
+            sum.wrapping_add(hasher.finish())
+        });
+
+        self.len().hash(state);
+        sum.hash(state);
+    }
+}
+
+impl fmt::Debug for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<(String, Value)> for Map {
+    fn from_iter<I: IntoIterator<Item = (String, Value)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl Extend<(String, Value)> for Map {
+    fn extend<I: IntoIterator<Item = (String, Value)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<const N: usize> From<[(String, Value); N]> for Map {
+    fn from(entries: [(String, Value); N]) -> Self {
+        Self::from_iter(entries)
+    }
+}
+
+pub struct Iter<'a> {
+    entries: slice::Iter<'a, (String, Value)>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a String, &'a Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|(key, value)| (key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Iter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back().map(|(key, value)| (key, value))
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+pub struct IterMut<'a> {
+    entries: slice::IterMut<'a, (String, Value)>,
+}
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = (&'a String, &'a mut Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|(key, value)| (&*key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for IterMut<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back().map(|(key, value)| (&*key, value))
+    }
+}
+
+impl<'a> ExactSizeIterator for IterMut<'a> {}
+
+pub struct IntoIter {
+    entries: vec::IntoIter<(String, Value)>,
+}
+
+impl Iterator for IntoIter {
+    type Item = (String, Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for IntoIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back()
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl IntoIterator for Map {
+    type Item = (String, Value);
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> IntoIter {
+        IntoIter { entries: self.entries.into_iter() }
+    }
+}
+
+impl<'a> IntoIterator for &'a Map {
+    type Item = (&'a String, &'a Value);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Map {
+    type Item = (&'a String, &'a mut Value);
+    type IntoIter = IterMut<'a>;
+
+    fn into_iter(self) -> IterMut<'a> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+
+    use crate::Value;
+    use super::Map;
+
+    fn map(keys: &[&str]) -> Map {
+        keys.iter()
+            .map(|key| (key.to_string(), Value::string(key)))
+            .collect()
+    }
+
+    fn keys(map: &Map) -> Vec<&str> {
+        map.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn keeps_insertion_order() {
+        let map = map(&["b", "c", "a"]);
+
+        assert_eq!(keys(&map), ["b", "c", "a"]);
+    }
+
+    #[test]
+    fn insert_existing_key_keeps_position() {
+        let mut map = map(&["a", "b"]);
+
+        let previous = map.insert("a".to_string(), Value::Null);
+
+        assert_eq!(previous, Some(Value::string("a")));
+        assert_eq!(keys(&map), ["a", "b"]);
+        assert_eq!(map.get("a"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn remove_keeps_order() {
+        let mut map = map(&["a", "b", "c"]);
+
+        assert_eq!(map.remove("b"), Some(Value::string("b")));
+        assert_eq!(map.remove("b"), None);
+        assert_eq!(keys(&map), ["a", "c"]);
+    }
+
+    #[test]
+    fn sort_keys() {
+        let mut map = map(&["b", "é", "a", "B"]);
+
+        map.sort_keys();
+
+        assert_eq!(keys(&map), ["B", "a", "b", "é"]);
+        assert_eq!(map.get("a"), Some(&Value::string("a")));
+    }
+
+    #[test]
+    fn entry() {
+        let mut map = map(&["a"]);
+
+        map.entry(String::from("a")).or_insert(Value::Null);
+        *map.entry(String::from("b")).or_insert(Value::number(1)) = Value::number(2);
+        map.entry(String::from("a")).and_modify(|value| *value = Value::Boolean(true)).or_insert(Value::Null);
+        map.entry(String::from("c")).and_modify(|value| *value = Value::Boolean(true)).or_insert_with(|| Value::Null);
+
+        assert_eq!(map.get("a"), Some(&Value::Boolean(true)));
+        assert_eq!(map.get("b"), Some(&Value::number(2)));
+        assert_eq!(map.get("c"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn lookups_follow_removal_and_sorting() {
+        let mut map = map(&["c", "a", "b", "d"]);
+
+        map.remove("a");
+        assert_eq!(keys(&map), ["c", "b", "d"]);
+        assert_eq!(map.get("d"), Some(&Value::string("d")));
+
+        map.sort_keys();
+        map.insert(String::from("c"), Value::Null);
+        assert_eq!(keys(&map), ["b", "c", "d"]);
+        assert_eq!(map.get("c"), Some(&Value::Null));
+        assert_eq!(map.get("b"), Some(&Value::string("b")));
+        assert_eq!(map.get("a"), None);
+    }
+
+    #[test]
+    fn equality_ignores_order() {
+        assert_eq!(map(&["a", "b"]), map(&["b", "a"]));
+        assert_ne!(map(&["a", "b"]), map(&["a"]));
+    }
+
+    #[test]
+    fn hash_ignores_order() {
+        let hash = |map: &Map| {
+            let mut hasher = DefaultHasher::new();
+            map.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        assert_eq!(hash(&map(&["a", "b", "c"])), hash(&map(&["c", "a", "b"])));
+        assert_ne!(hash(&map(&["a", "b"])), hash(&map(&["a"])));
+    }
+}
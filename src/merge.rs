@@ -0,0 +1,142 @@
+use crate::{Map, Value};
+
+impl Value {
+    /// Applies an RFC 7386 JSON Merge Patch: objects are merged recursively,
+    /// `null` members of the patch remove keys and any other patch replaces the value
+    pub fn merge(&mut self, patch: &Value) {
+        let Value::Object(patch) = patch else {
+            *self = patch.clone();
+            return;
+        };
+
+        if !matches!(self, Value::Object(_)) {
+            *self = Value::Object(Map::new());
+        }
+        let Value::Object(map) = self else {
+            unreachable!("value was replaced with an object above");
+        };
+
+        for (key, value) in patch {
+            match value {
+                Value::Null => {
+                    map.remove(key);
+                }
+                value => match map.get_mut(key) {
+                    Some(target) => target.merge(value),
+                    None => {
+                        let mut target = Value::Null;
+                        target.merge(value);
+                        map.insert(key.clone(), target);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Merges other into this value the way layered configuration is: objects are merged recursively,
+    /// two arrays are combined as `arrays` says and any other value, `null` included, replaces this one
+    pub fn deep_merge(&mut self, other: &Value, arrays: ArrayMerge) {
+        match (self, other) {
+            (Value::Object(map), Value::Object(other)) => {
+                for (key, value) in other {
+                    match map.get_mut(key) {
+                        Some(target) => target.deep_merge(value, arrays),
+                        None => {
+                            map.insert(key.clone(), value.clone());
+                        }
+                    }
+                }
+            }
+            (Value::Array(array), Value::Array(other)) if arrays == ArrayMerge::Concatenate => {
+                array.extend(other.iter().cloned());
+            }
+            (target, other) => *target = other.clone(),
+        }
+    }
+}
+
+/// How [`Value::deep_merge`] combines two arrays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayMerge {
+    /// Append the elements of the other array
+    Concatenate,
+    /// Replace the array with the other one, like any other value
+    Replace,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, Value};
+    use super::ArrayMerge;
+
+    fn check(target: &str, patch: &str, expected: &str) {
+        let mut actual = parse(String::from(target)).unwrap();
+        let patch = parse(String::from(patch)).unwrap();
+        let expected = parse(String::from(expected)).unwrap();
+
+        actual.merge(&patch);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn merges_objects_recursively() {
+        check(
+            r#"{"a": "b", "c": {"d": "e", "f": "g"}}"#,
+            r#"{"a": "z", "c": {"f": null}}"#,
+            r#"{"a": "z", "c": {"d": "e"}}"#,
+        );
+    }
+
+    #[test]
+    fn null_removes_key() {
+        check(r#"{"a": "b", "b": "c"}"#, r#"{"a": null}"#, r#"{"b": "c"}"#);
+        check(r#"{"a": "b"}"#, r#"{"missing": null}"#, r#"{"a": "b"}"#);
+    }
+
+    #[test]
+    fn non_object_patch_replaces() {
+        check(r#"{"a": "c"}"#, r#"["c"]"#, r#"["c"]"#);
+        check(r#"{"a": "c"}"#, "null", "null");
+        check(r#"["a", "b"]"#, r#"["c", "d"]"#, r#"["c", "d"]"#);
+    }
+
+    #[test]
+    fn object_patch_replaces_non_object() {
+        check(r#"["a"]"#, r#"{"a": "b"}"#, r#"{"a": "b"}"#);
+        check(r#"{"a": "foo"}"#, r#"{"a": {"bb": {"ccc": null}}}"#, r#"{"a": {"bb": {}}}"#);
+    }
+
+    #[test]
+    fn nulls_inside_new_members_are_dropped() {
+        let mut value = Value::object([]);
+        let patch = Value::object([("a", Value::object([("b", Value::Null), ("c", Value::number(1))]))]);
+
+        value.merge(&patch);
+
+        assert_eq!(value, Value::object([("a", Value::object([("c", Value::number(1))]))]));
+    }
+
+    #[test]
+    fn deep_merge_objects() {
+        let mut value = json!({"server": {"host": "localhost", "port": 80}, "debug": true});
+
+        value.deep_merge(&json!({"server": {"port": 8080, "tls": null}, "debug": {"level": 2}}), ArrayMerge::Replace);
+
+        assert_eq!(value, json!({"server": {"host": "localhost", "port": 8080, "tls": null}, "debug": {"level": 2}}));
+    }
+
+    #[test]
+    fn deep_merge_arrays() {
+        let defaults = json!({"plugins": ["a", "b"], "nested": [{"x": 1}]});
+        let overrides = json!({"plugins": ["c"], "nested": "none"});
+
+        let mut concatenated = defaults.clone();
+        concatenated.deep_merge(&overrides, ArrayMerge::Concatenate);
+        let mut replaced = defaults;
+        replaced.deep_merge(&overrides, ArrayMerge::Replace);
+
+        assert_eq!(concatenated, json!({"plugins": ["a", "b", "c"], "nested": "none"}));
+        assert_eq!(replaced, json!({"plugins": ["c"], "nested": "none"}));
+    }
+}
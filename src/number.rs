@@ -0,0 +1,292 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// JSON number that remembers whether it was written as an integer
+///
+/// Numbers compare by their numeric value, so `1` equals `1.0` and `-0.0` equals `0`.
+/// Every `NaN` equals every other `NaN`, which makes the comparison total.
+/// Use [`Number::is_f64`] to tell the two forms apart.
+#[derive(Clone, Copy)]
+pub struct Number {
+    n: N,
+}
+
+#[derive(Clone, Copy)]
+enum N {
+    /// Always greater than or equal to zero
+    PosInt(u128),
+    /// Always less than zero
+    NegInt(i128),
+    Float(f64),
+}
+
+impl Number {
+    /// Number was written without a fraction and an exponent and fits into `i64`
+    pub fn is_i64(&self) -> bool {
+        self.as_i64().is_some()
+    }
+
+    /// Number was written without a fraction and an exponent and fits into `u64`
+    pub fn is_u64(&self) -> bool {
+        self.as_u64().is_some()
+    }
+
+    /// Number was written without a fraction and an exponent and fits into `i128`
+    pub fn is_i128(&self) -> bool {
+        self.as_i128().is_some()
+    }
+
+    /// Number was written without a fraction and an exponent and fits into `u128`
+    pub fn is_u128(&self) -> bool {
+        matches!(self.n, N::PosInt(_))
+    }
+
+    /// Number was written with a fraction or an exponent, or doesn't fit any integer type
+    pub fn is_f64(&self) -> bool {
+        matches!(self.n, N::Float(_))
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_i128().and_then(|i| i64::try_from(i).ok())
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_u128().and_then(|u| u64::try_from(u).ok())
+    }
+
+    pub fn as_i128(&self) -> Option<i128> {
+        match self.n {
+            N::PosInt(u) => i128::try_from(u).ok(),
+            N::NegInt(i) => Some(i),
+            N::Float(_) => None,
+        }
+    }
+
+    pub fn as_u128(&self) -> Option<u128> {
+        match self.n {
+            N::PosInt(u) => Some(u),
+            N::NegInt(_) | N::Float(_) => None,
+        }
+    }
+
+    /// Integers are converted, possibly losing precision
+    pub fn as_f64(&self) -> f64 {
+        match self.n {
+            N::PosInt(u) => u as f64,
+            N::NegInt(i) => i as f64,
+            N::Float(f) => f,
+        }
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.n, other.n) {
+            (N::PosInt(a), N::PosInt(b)) => a == b,
+            (N::NegInt(a), N::NegInt(b)) => a == b,
+            // unlike `f64`, `NaN` equals itself so that `Number` can be `Eq`
+            (N::Float(a), N::Float(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (N::PosInt(u), N::Float(f)) | (N::Float(f), N::PosInt(u)) => {
+                // the range check keeps the saturating cast from matching out-of-range floats
+                f.fract() == 0.0 && f >= 0.0 && f < u128::MAX as f64 && f as u128 == u
+            }
+            (N::NegInt(i), N::Float(f)) | (N::Float(f), N::NegInt(i)) => {
+                f.fract() == 0.0 && f >= i128::MIN as f64 && f < 0.0 && f as i128 == i
+            }
+            (N::PosInt(_), N::NegInt(_)) | (N::NegInt(_), N::PosInt(_)) => false,
+        }
+    }
+}
+
+impl Eq for Number {}
+
+/// Hashes the numeric value like equality compares it: whole floats hash as the
+/// integer they equal, other floats by their bits with a single bit pattern for `NaN`
+impl Hash for Number {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // integers are hashed as sign and magnitude, which covers both `u128` and `i128`
+        match self.n {
+            N::PosInt(u) => (false, u).hash(state),
+            N::NegInt(i) => (true, i.unsigned_abs()).hash(state),
+            // the range covers exactly the floats that can equal `PosInt` or `NegInt`,
+            // `-0.0` included
+            N::Float(f) if f.fract() == 0.0 && f >= 0.0 && f < u128::MAX as f64 => (false, f as u128).hash(state),
+            N::Float(f) if f.fract() == 0.0 && f >= i128::MIN as f64 && f < 0.0 => {
+                (true, (f as i128).unsigned_abs()).hash(state)
+            }
+            N::Float(f) if f.is_nan() => f64::NAN.to_bits().hash(state),
+            N::Float(f) => f.to_bits().hash(state),
+        }
+    }
+}
+
+impl fmt::Debug for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.n {
+            N::PosInt(u) => write!(f, "Number({u})"),
+            N::NegInt(i) => write!(f, "Number({i})"),
+            N::Float(float) => write!(f, "Number({float:?})"),
+        }
+    }
+}
+
+impl From<f64> for Number {
+    fn from(f: f64) -> Self {
+        Self { n: N::Float(f) }
+    }
+}
+
+impl From<f32> for Number {
+    fn from(f: f32) -> Self {
+        Self { n: N::Float(f64::from(f)) }
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Number {
+                fn from(u: $ty) -> Self {
+                    Self { n: N::PosInt(u as u128) }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! from_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Number {
+                fn from(i: $ty) -> Self {
+                    if i < 0 {
+                        Self { n: N::NegInt(i as i128) }
+                    } else {
+                        Self { n: N::PosInt(i as u128) }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Writes integers as is and whole floats without `.0`, so the integer/float distinction doesn't survive text
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.n {
+            N::PosInt(u) => write!(f, "{u}"),
+            N::NegInt(i) => write!(f, "{i}"),
+            // `Display` for `f64` prints the shortest string that round-trips and never an exponent,
+            // whole floats like `5.0` come out as `5`, the way other JSON producers write them
+            N::Float(float) => write!(f, "{float}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+
+    use super::Number;
+
+    fn hash(number: impl Into<Number>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        number.into().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn integer_equals_whole_float() {
+        assert_eq!(Number::from(1), Number::from(1.0));
+        assert_eq!(Number::from(-3), Number::from(-3.0));
+        assert_ne!(Number::from(1), Number::from(1.5));
+    }
+
+    #[test]
+    fn nan_equals_nan() {
+        assert_eq!(Number::from(f64::NAN), Number::from(f64::NAN));
+        assert_eq!(Number::from(f64::NAN), Number::from(-f64::NAN));
+        assert_ne!(Number::from(f64::NAN), Number::from(0));
+        assert_eq!(Number::from(-0.0), Number::from(0.0));
+    }
+
+    #[test]
+    fn equal_numbers_hash_equally() {
+        assert_eq!(hash(1), hash(1.0));
+        assert_eq!(hash(-3), hash(-3.0));
+        assert_eq!(hash(0), hash(-0.0));
+        assert_eq!(hash(u64::MAX - 2047), hash((u64::MAX - 2047) as f64));
+        assert_eq!(hash(i64::MIN), hash(i64::MIN as f64));
+        assert_eq!(hash(f64::NAN), hash(-f64::NAN));
+    }
+
+    #[test]
+    fn different_numbers_hash_differently() {
+        assert_ne!(hash(1), hash(1.5));
+        assert_ne!(hash(9007199254740993u64), hash(9007199254740992.0));
+        assert_ne!(hash(0.1), hash(0.2));
+    }
+
+    #[test]
+    fn large_integer_not_equal_to_rounded_float() {
+        let integer = Number::from(9007199254740993u64);
+        let float = Number::from(9007199254740992.0);
+
+        assert_ne!(integer, float);
+    }
+
+    #[test]
+    fn integer_accessors() {
+        let number = Number::from(u64::MAX);
+
+        assert!(number.is_u64());
+        assert!(!number.is_i64());
+        assert_eq!(number.as_u64(), Some(u64::MAX));
+        assert_eq!(number.as_i64(), None);
+    }
+
+    #[test]
+    fn wide_integer_accessors() {
+        let unsigned = Number::from(u128::MAX);
+        let signed = Number::from(i128::MIN);
+
+        assert!(unsigned.is_u128() && !unsigned.is_i128() && !unsigned.is_u64());
+        assert_eq!(unsigned.as_u128(), Some(u128::MAX));
+        assert!(signed.is_i128() && !signed.is_u128() && !signed.is_i64());
+        assert_eq!(signed.as_i128(), Some(i128::MIN));
+        assert_eq!(Number::from(-5).as_i128(), Some(-5));
+    }
+
+    #[test]
+    fn wide_integer_equals_whole_float() {
+        let two_to_100 = 1u128 << 100;
+
+        assert_eq!(Number::from(two_to_100), Number::from(two_to_100 as f64));
+        assert_eq!(Number::from(-(two_to_100 as i128)), Number::from(-(two_to_100 as f64)));
+        assert_ne!(Number::from(two_to_100 + 1), Number::from(two_to_100 as f64));
+        assert_eq!(hash(two_to_100), hash(two_to_100 as f64));
+        assert_eq!(hash(i128::MIN), hash(i128::MIN as f64));
+    }
+
+    #[test]
+    fn float_accessors() {
+        let number = Number::from(1.0);
+
+        assert!(number.is_f64());
+        assert_eq!(number.as_i64(), None);
+        assert_eq!(number.as_f64(), 1.0);
+    }
+
+    #[test]
+    fn displays_whole_float_without_fraction() {
+        assert_eq!(Number::from(5.0).to_string(), "5");
+        assert_eq!(Number::from(1415436218769.0).to_string(), "1415436218769");
+        assert_eq!(Number::from(-2.0).to_string(), "-2");
+        assert_eq!(Number::from(0.25).to_string(), "0.25");
+        assert_eq!(Number::from(-1.5).to_string(), "-1.5");
+        assert_eq!(Number::from(-5).to_string(), "-5");
+    }
+}
@@ -0,0 +1,99 @@
+use crate::Value;
+
+/// Converts the text of a number literal, see [`ParseOptions::number_parser`]
+pub type NumberParser = fn(&str) -> Result<Value, String>;
+
+/// Settings that relax or restrict what the parser accepts
+///
+/// The default options parse strict RFC 8259 JSON.
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// Maximum nesting of arrays and objects, a top-level container is at depth 1
+    ///
+    /// Defaults to 128, which is plenty for real documents. Deeper trees can be parsed
+    /// after raising it, but dropping them recurses as deep as they are nested.
+    pub max_depth: usize,
+    /// Accept a comma right before the closing `]` or `}`
+    pub allow_trailing_commas: bool,
+    /// Skip `// line` and `/* block */` comments between tokens
+    pub allow_comments: bool,
+    /// Accept `NaN`, `Infinity` and `-Infinity` as numbers
+    pub allow_nan_inf: bool,
+    /// Keep number literals as written in [`Value::RawNumber`](crate::Value::RawNumber)
+    /// instead of converting them
+    pub preserve_number_text: bool,
+    /// Hands every number literal, as written, to this function instead of converting it to a [`Number`](crate::Number)
+    ///
+    /// The value it returns is used in place of the number, like a decimal kept in a string,
+    /// and an error fails the parse. `NaN` and infinities are not number literals and don't reach it.
+    pub number_parser: Option<NumberParser>,
+    /// Replace `\u` escapes of unpaired UTF-16 surrogates with U+FFFD instead of failing
+    pub replace_unpaired_surrogates: bool,
+    /// Accept object keys written as bare identifiers, like `{name: "x"}`
+    pub allow_unquoted_keys: bool,
+    /// Accept strings in `'single quotes'`, along with the `\'` escape
+    pub allow_single_quotes: bool,
+    /// Decode the non-standard escapes `\v` (vertical tab), `\0` (NUL) and `\xHH` (code point
+    /// below U+0100) that some producers emit, and take any other unknown escape like `\q`
+    /// as the character after the backslash instead of failing with
+    /// [`TokenizeError::InvalidEscape`](crate::TokenizeError::InvalidEscape)
+    pub lenient_escapes: bool,
+    /// Longest string accepted, in bytes of its UTF-8 contents after unescaping
+    ///
+    /// Unlimited by default, set it when parsing untrusted input so a single huge
+    /// string can't exhaust memory.
+    pub max_string_length: Option<usize>,
+    /// Most elements accepted in a single array or entries in a single object, unlimited by default
+    pub max_elements: Option<usize>,
+    /// What happens when an object repeats a key
+    pub duplicate_keys: DuplicateKeyPolicy,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            allow_trailing_commas: false,
+            allow_comments: false,
+            allow_nan_inf: false,
+            preserve_number_text: false,
+            number_parser: None,
+            replace_unpaired_surrogates: false,
+            allow_unquoted_keys: false,
+            allow_single_quotes: false,
+            lenient_escapes: false,
+            max_string_length: None,
+            max_elements: None,
+            duplicate_keys: DuplicateKeyPolicy::LastWins,
+        }
+    }
+}
+
+/// Handling of a key that appears more than once in the same object
+///
+/// Consumers disagree on which value counts, picking the one another consumer picks
+/// keeps both from seeing different documents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateKeyPolicy {
+    /// Keep the value of the first occurrence
+    FirstWins,
+    /// Keep the value of the last occurrence, at the position of the first one
+    #[default]
+    LastWins,
+    /// Fail with [`TokenParseError::DuplicateKey`](crate::TokenParseError::DuplicateKey)
+    Error,
+}
+
+/// Settings that change how values are written
+///
+/// The default options write compact JSON with non-ASCII characters as raw UTF-8.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SerializeOptions {
+    /// Write every non-ASCII character as a `\uXXXX` escape, using surrogate pairs above U+FFFF
+    pub ascii_only: bool,
+    /// Write `/` as `\/`, so a string holding `</script>` can't end a script it is embedded in
+    pub escape_forward_slashes: bool,
+    /// Round floats to at most this many decimal places, dropping trailing zeros, integers are
+    /// written whole. `None` writes the shortest text that parses back to the same float.
+    pub float_precision: Option<usize>,
+}
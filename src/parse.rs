@@ -1,163 +1,433 @@
-use std::{collections::HashMap, iter::Peekable, vec::IntoIter};
+use std::{error::Error, fmt, iter::Peekable};
 
-use crate::{Value, tokenize::Token};
+use crate::{DuplicateKeyPolicy, Location, Map, ParseError, ParseOptions, Span, Value, tokenize::{Cursor, SpannedToken, Token, TokenizeError, Tokenizer}};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TokenParseError {
-    UnfinishedEscape,
-    InvalidHexValue,
-    InvalidCodePointValue,
-    ExpectedComma,
-    ExpectedProperty,
-    ExpectedColon
+    /// Something other than `,` or the closing bracket follows an element, holds what was found instead
+    ExpectedComma { found: Token, location: Location },
+    ExpectedProperty { location: Location },
+    /// Something other than `:` follows an object key, holds what was found instead, which is left unconsumed
+    ExpectedColon { found: Token, location: Location },
+    UnexpectedEof,
+    /// Nesting of arrays and objects is deeper than `ParseOptions::max_depth`
+    DepthLimitExceeded { location: Location },
+    /// Comma right before `]` or `}` while `ParseOptions::allow_trailing_commas` is off
+    TrailingComma { location: Location },
+    /// Array or object with more elements than `ParseOptions::max_elements`,
+    /// holds where the first element over the limit starts
+    TooManyElements { location: Location },
+    /// Token that can't appear where it was found, like `]` where a value is expected
+    UnexpectedToken { token: Token, location: Location },
+    /// Closing bracket, comma or colon where the document should start, which usually means
+    /// the brackets of the input are unbalanced
+    UnexpectedAtTopLevel { token: Token, location: Location },
+    /// `ParseOptions::number_parser` failed on the number literal, holds the message it returned
+    NumberParserFailed { message: String, location: Location },
+    /// Key repeated in an object while `ParseOptions::duplicate_keys` is `DuplicateKeyPolicy::Error`,
+    /// holds where the repeated key starts
+    DuplicateKey { key: String, location: Location },
 }
 
-pub fn parse_tokens(tokens: &mut Peekable<IntoIter<Token>>) -> Result<Value, TokenParseError> {
-    let token = tokens.next().unwrap();
+impl fmt::Display for TokenParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExpectedComma { found, location } => write!(f, "expected `,` but found {} at {location}", describe(found)),
+            Self::ExpectedProperty { location } => write!(f, "expected string key at {location}"),
+            Self::ExpectedColon { found, location } => write!(f, "expected `:` but found {} at {location}", describe(found)),
+            Self::UnexpectedEof => f.write_str("unexpected end of input"),
+            Self::DepthLimitExceeded { location } => write!(f, "nesting too deep at {location}"),
+            Self::TrailingComma { location } => write!(f, "trailing comma at {location}"),
+            Self::TooManyElements { location } => write!(f, "too many elements at {location}"),
+            Self::UnexpectedToken { token, location } => write!(f, "unexpected {} at {location}", describe(token)),
+            Self::UnexpectedAtTopLevel { token, location } => {
+                write!(f, "unexpected {} at top level, {location}", describe(token))
+            }
+            Self::NumberParserFailed { message, location } => write!(f, "invalid number at {location}: {message}"),
+            Self::DuplicateKey { key, location } => write!(f, "duplicate key {key:?} at {location}"),
+        }
+    }
+}
 
-    match token {
-        Token::Null => Ok(Value::Null),
-        Token::True => Ok(Value::Boolean(true)),    
-        Token::False => Ok(Value::Boolean(false)),
-        Token::Number(number) => Ok(Value::Number(number)),
-        Token::String(string) => parse_string(&string),
-        Token::LeftBracket => parse_array(tokens),
-        Token::LeftBrace => parse_objects(tokens),
-        _ => todo!()
+impl TokenParseError {
+    /// Where in the source the error was found, `None` when the input ended too early
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Self::ExpectedComma { location, .. }
+            | Self::ExpectedProperty { location }
+            | Self::ExpectedColon { location, .. }
+            | Self::DepthLimitExceeded { location }
+            | Self::TrailingComma { location }
+            | Self::TooManyElements { location }
+            | Self::UnexpectedToken { location, .. }
+            | Self::UnexpectedAtTopLevel { location, .. }
+            | Self::NumberParserFailed { location, .. }
+            | Self::DuplicateKey { location, .. } => Some(*location),
+            Self::UnexpectedEof => None,
+        }
     }
 }
 
-fn parse_string(input: &str) -> Result<Value, TokenParseError> {
-    let unescaped = unescape_string(input)?;
-    Ok(Value::String(unescaped))
+impl Error for TokenParseError {}
+
+/// Names a token the way it reads in the source, so messages read like "unexpected closing brace `}`"
+/// or "found number `2`"
+fn describe(token: &Token) -> String {
+    let name = match token {
+        Token::LeftBrace => "opening brace `{`",
+        Token::RightBrace => "closing brace `}`",
+        Token::LeftBracket => "opening bracket `[`",
+        Token::RightBracket => "closing bracket `]`",
+        Token::Comma => "comma `,`",
+        Token::Colon => "colon `:`",
+        Token::Null => "`null`",
+        Token::True => "`true`",
+        Token::False => "`false`",
+        Token::Number(number) => return format!("number `{number}`"),
+        Token::RawNumber(text) => return format!("number `{text}`"),
+        Token::String(string) => return format!("string {string:?}"),
+    };
+
+    String::from(name)
 }
 
-fn unescape_string(input: &str) -> Result<String, TokenParseError> {
-    let mut output = String::new();
-
-    let mut is_escaping = false;
-
-    let mut chars = input.chars();
-    while let Some(next_char) = chars.next() {
-        if is_escaping {
-            match next_char {
-                '"' => output.push('"'),
-                '\\' => output.push('\\'),
-                // `\b` (backspace) is a valid escape in JSON, but not Rust
-                'b' => output.push('\u{8}'),
-                // `\f` (formfeed) is a valid escape in JSON, but not Rust
-                'f' => output.push('\u{12}'),
-                'n' => output.push('\n'),
-                'r' => output.push('\r'),
-                't' => output.push('\t'),
-                'u' => {
-                    let mut sum = 0;
-                    for i in 0..4 {
-                        let next_char = chars.next().ok_or(TokenParseError::UnfinishedEscape)?;
-                        let digit = next_char
-                            .to_digit(16)
-                            .ok_or(TokenParseError::InvalidHexValue)?;
-                        sum += (16u32).pow(3 - i) * digit;
-                    }
-                    let unescaped_char = char::from_u32(sum).ok_or(TokenParseError::InvalidCodePointValue)?;
-                    output.push(unescaped_char);
-                },
-                // any other character *may* be escaped, ex. `\q` just push that letter `q`
-                _ => output.push(next_char),
-            }
-            is_escaping = false;
-        } 
-        else if next_char == '\\' {
-            is_escaping = true;
-        } 
-        else {
-            output.push(next_char);
+/// Error for a token that can't start a value, `top_level` when no container is open
+pub(crate) fn unexpected_token(token: Token, location: Location, top_level: bool) -> TokenParseError {
+    if top_level {
+        TokenParseError::UnexpectedAtTopLevel { token, location }
+    } else {
+        TokenParseError::UnexpectedToken { token, location }
+    }
+}
+
+/// Token a [`TokenStream`] can hold, like [`SpannedToken`] or one whose string borrows from the input
+pub trait StreamToken: Sized {
+    /// Contents of a string token
+    type Str;
+
+    fn span(&self) -> Span;
+
+    /// Whether this is `token`, which is punctuation or a literal
+    fn is(&self, token: &Token) -> bool;
+
+    /// Contents of a string token, the token itself for any other
+    fn into_string(self) -> Result<Self::Str, Self>;
+
+    /// Owned copy for error messages
+    fn to_token(&self) -> Token;
+}
+
+impl StreamToken for SpannedToken {
+    type Str = String;
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn is(&self, token: &Token) -> bool {
+        self.token == *token
+    }
+
+    fn into_string(self) -> Result<String, Self> {
+        match self.token {
+            Token::String(string) => Ok(string),
+            token => Err(SpannedToken { token, span: self.span }),
         }
     }
 
-    Ok(output)
+    fn to_token(&self) -> Token {
+        self.token.clone()
+    }
 }
 
-fn parse_array(tokens: &mut Peekable<IntoIter<Token>>) -> Result<Value, TokenParseError> {
-    let mut array: Vec<Value> = Vec::new();
+/// Peekable stream of tokens that surfaces tokenizer errors as the parser pulls tokens
+pub struct TokenStream<I: Iterator> {
+    tokens: Peekable<I>,
+    /// End of the token most recently returned by `next`
+    end: Location,
+}
 
-    loop {
-        if *tokens.peek().unwrap() == Token::RightBracket {
-            break;
+impl<I: Iterator> TokenStream<I> {
+    pub fn new(tokens: I) -> Self {
+        Self { tokens: tokens.peekable(), end: Location::start() }
+    }
+
+    /// Location right after the last consumed token
+    pub fn end(&self) -> Location {
+        self.end
+    }
+}
+
+impl<I, T> TokenStream<I>
+where
+    I: Iterator<Item = Result<T, TokenizeError>>,
+    T: StreamToken,
+{
+    /// Next token, `None` at the end of input
+    pub fn next(&mut self) -> Result<Option<T>, ParseError> {
+        let token = self.tokens.next().transpose()?;
+        if let Some(token) = &token {
+            self.end = token.span().end;
         }
-        
-        let value = parse_tokens(tokens)?;
-        array.push(value);
-        
-        let token = tokens.next().unwrap();
-        match token {
-            Token::Comma => continue,
-            Token::RightBracket => return Ok(Value::Array(array)),
-            _ => return Err(TokenParseError::ExpectedComma),
+
+        Ok(token)
+    }
+
+    pub fn peek(&mut self) -> Result<Option<&T>, ParseError> {
+        if let Some(Err(_)) = self.tokens.peek() {
+            let Some(Err(error)) = self.tokens.next() else { unreachable!("an error was just peeked") };
+            return Err(error.into());
         }
+
+        Ok(self.tokens.peek().and_then(|token| token.as_ref().ok()))
     }
 
-    tokens.next();
+    /// Next token, the end of input is an error
+    pub(crate) fn next_token(&mut self) -> Result<T, ParseError> {
+        self.next()?.ok_or(TokenParseError::UnexpectedEof.into())
+    }
 
-    Ok(Value::Array(array))
+    pub(crate) fn peek_is(&mut self, token: &Token) -> Result<bool, ParseError> {
+        Ok(self.peek()?.is_some_and(|peeked| peeked.is(token)))
+    }
 }
 
-fn parse_objects(tokens: &mut Peekable<IntoIter<Token>>) -> Result<Value, TokenParseError> {
-    let mut map = HashMap::new();
+/// Stream over the tokens of a whole document, failing with `ParseError::EmptyInput`
+/// or `ParseError::BlankInput` when there are none
+pub(crate) fn document_tokens<C: Cursor>(
+    mut tokenizer: Tokenizer<C>,
+) -> Result<TokenStream<impl Iterator<Item = Result<SpannedToken, TokenizeError>>>, ParseError> {
+    let Some(first) = tokenizer.next() else {
+        return Err(ParseError::no_value(tokenizer.location()));
+    };
 
-    loop {
-        if let Some(&Token::RightBrace) = tokens.peek() {
-            break;
-        }
+    Ok(TokenStream::new(std::iter::once(first).chain(tokenizer)))
+}
 
-        if let Some(Token::String(s)) = tokens.next() {
-            if let Some(Token::Colon) = tokens.next() {
-                let key = unescape_string(&s)?;
-                let value = parse_tokens(tokens)?;
-                map.insert(key, value);
-            } else {
-                return Err(TokenParseError::ExpectedColon)
-            }
-        } else {
-            return Err(TokenParseError::ExpectedProperty)
+pub fn parse_tokens<I>(tokens: &mut TokenStream<I>, options: &ParseOptions) -> Result<Value, ParseError>
+where
+    I: Iterator<Item = Result<SpannedToken, TokenizeError>>,
+{
+    parse_value(tokens, options)
+}
+
+/// Container that is still being parsed
+///
+/// Both count the elements started so far, see `ParseOptions::max_elements`.
+enum Frame {
+    Array(Vec<Value>, usize),
+    /// Object together with the key of the value being parsed
+    Object(Map, String, usize),
+}
+
+/// Parses a value using an explicit stack of open containers instead of recursion,
+/// so adversarial nesting can't overflow the native stack
+fn parse_value<I>(tokens: &mut TokenStream<I>, options: &ParseOptions) -> Result<Value, ParseError>
+where
+    I: Iterator<Item = Result<SpannedToken, TokenizeError>>,
+{
+    let mut stack: Vec<Frame> = Vec::new();
+
+    'value: loop {
+        let SpannedToken { token, span } = tokens.next_token()?;
+
+        if matches!(token, Token::LeftBracket | Token::LeftBrace) && stack.len() >= options.max_depth {
+            return Err(TokenParseError::DepthLimitExceeded { location: span.start }.into());
         }
 
-        match tokens.peek() {
-            Some(Token::Comma) => {
-                tokens.next();
+        let mut value = match token {
+            Token::Null => Value::Null,
+            Token::True => Value::Boolean(true),
+            Token::False => Value::Boolean(false),
+            Token::Number(number) => Value::Number(number),
+            Token::RawNumber(text) => match options.number_parser {
+                Some(number_parser) => number_parser(&text)
+                    .map_err(|message| TokenParseError::NumberParserFailed { message, location: span.start })?,
+                None => Value::RawNumber(text),
+            },
+            Token::String(string) => Value::String(string),
+            Token::LeftBracket => {
+                if tokens.peek_is(&Token::RightBracket)? {
+                    tokens.next()?;
+                    Value::Array(Vec::new())
+                } else {
+                    count_element(&mut 0, tokens, options)?;
+                    stack.push(Frame::Array(Vec::new(), 1));
+                    continue 'value;
+                }
+            }
+            Token::LeftBrace => {
+                if tokens.peek_is(&Token::RightBrace)? {
+                    tokens.next()?;
+                    Value::Object(Map::new())
+                } else {
+                    count_element(&mut 0, tokens, options)?;
+                    let (key, _) = parse_property_name(tokens)?;
+                    stack.push(Frame::Object(Map::new(), key, 1));
+                    continue 'value;
+                }
             }
-            Some(Token::RightBrace) => {
-                break;
+            token => return Err(unexpected_token(token, span.start, stack.is_empty()).into()),
+        };
+
+        // hand the finished value to its container, closing every container that ends with it
+        loop {
+            match stack.last_mut() {
+                None => return Ok(value),
+                Some(Frame::Array(array, length)) => {
+                    array.push(value);
+
+                    let SpannedToken { token, span } = tokens.next_token()?;
+                    match token {
+                        Token::Comma if !tokens.peek_is(&Token::RightBracket)? => {
+                            count_element(length, tokens, options)?;
+                            continue 'value;
+                        }
+                        Token::Comma if options.allow_trailing_commas => {
+                            tokens.next()?;
+                        }
+                        Token::Comma => {
+                            return Err(TokenParseError::TrailingComma { location: span.start }.into())
+                        }
+                        Token::RightBracket => {}
+                        found => return Err(TokenParseError::ExpectedComma { found, location: span.start }.into()),
+                    }
+                }
+                Some(Frame::Object(map, key, length)) => {
+                    let name = std::mem::take(key);
+                    if options.duplicate_keys != DuplicateKeyPolicy::FirstWins || !map.contains_key(&name) {
+                        map.insert(name, value);
+                    }
+
+                    match tokens.peek()? {
+                        Some(SpannedToken { token: Token::Comma, span }) => {
+                            let location = span.start;
+                            tokens.next()?;
+                            if !tokens.peek_is(&Token::RightBrace)? {
+                                count_element(length, tokens, options)?;
+                                let (next, location) = parse_property_name(tokens)?;
+                                if options.duplicate_keys == DuplicateKeyPolicy::Error && map.contains_key(&next) {
+                                    return Err(TokenParseError::DuplicateKey { key: next, location }.into());
+                                }
+
+                                *key = next;
+                                continue 'value;
+                            }
+
+                            if !options.allow_trailing_commas {
+                                return Err(TokenParseError::TrailingComma { location }.into());
+                            }
+                            tokens.next()?;
+                        }
+                        Some(SpannedToken { token: Token::RightBrace, .. }) => {
+                            tokens.next()?;
+                        }
+                        Some(SpannedToken { token, span }) => {
+                            let error = TokenParseError::ExpectedComma { found: token.clone(), location: span.start };
+                            return Err(error.into());
+                        }
+                        None => return Err(TokenParseError::UnexpectedEof.into()),
+                    }
+                }
             }
-            _ => return Err(TokenParseError::ExpectedComma)
+
+            value = match stack.pop() {
+                Some(Frame::Array(array, _)) => Value::Array(array),
+                Some(Frame::Object(map, _, _)) => Value::Object(map),
+                None => unreachable!("a container was just closed"),
+            };
         }
     }
+}
 
-    tokens.next();
+/// Counts the element about to be parsed, failing at its start when there are too many
+pub(crate) fn count_element<I, T>(length: &mut usize, tokens: &mut TokenStream<I>, options: &ParseOptions) -> Result<(), ParseError>
+where
+    I: Iterator<Item = Result<T, TokenizeError>>,
+    T: StreamToken,
+{
+    *length += 1;
+    if options.max_elements.is_some_and(|max| *length > max) {
+        let next = tokens.peek()?.ok_or(TokenParseError::UnexpectedEof)?;
+        return Err(TokenParseError::TooManyElements { location: next.span().start }.into());
+    }
 
-    Ok(Value::Object(map))
+    Ok(())
+}
+
+/// Parses an object key and the colon after it, returning the key and where it starts
+///
+/// A token other than the colon is left in the stream.
+pub(crate) fn parse_property_name<I, T>(tokens: &mut TokenStream<I>) -> Result<(T::Str, Location), ParseError>
+where
+    I: Iterator<Item = Result<T, TokenizeError>>,
+    T: StreamToken,
+{
+    let token = tokens.next_token()?;
+    let location = token.span().start;
+    let Ok(key) = token.into_string() else {
+        return Err(TokenParseError::ExpectedProperty { location }.into());
+    };
+
+    match tokens.peek()? {
+        Some(next) if next.is(&Token::Colon) => {
+            tokens.next()?;
+            Ok((key, location))
+        }
+        Some(found) => Err(TokenParseError::ExpectedColon { found: found.to_token(), location: found.span().start }.into()),
+        None => Err(TokenParseError::UnexpectedEof.into()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
-    use std::iter::Peekable;
     use std::vec::IntoIter;
 
-    use crate::tokenize::Token;
-    use crate::Value;
-    use super::parse_tokens;
+    use crate::tokenize::{SpannedToken, Token, TokenizeError};
+    use crate::{Location, Map, ParseError, ParseOptions, Span, Value};
+    use super::{parse_tokens, TokenParseError, TokenStream};
+
+    type Input = TokenStream<IntoIter<Result<SpannedToken, TokenizeError>>>;
+
+    /// Places every token on its own column, so error locations point at token indices
+    fn input(tokens: Vec<Token>) -> Input {
+        let tokens: Vec<_> = tokens
+            .into_iter()
+            .enumerate()
+            .map(|(i, token)| Ok(SpannedToken { token, span: Span { start: at(i), end: at(i + 1) } }))
+            .collect();
+
+        TokenStream::new(tokens.into_iter())
+    }
 
-    fn input(tokens: Vec<Token>) -> Peekable<IntoIter<Token>> {
-        tokens.into_iter().peekable()
+    fn at(index: usize) -> Location {
+        Location::new(1, index + 1, index)
     }
 
-    fn check(mut input: Peekable<IntoIter<Token>>, expected: Value) {
-        let actual = parse_tokens(&mut input).unwrap();
+    fn check(mut input: Input, expected: Value) {
+        let actual = parse_tokens(&mut input, &ParseOptions::default()).unwrap();
+
+        assert_eq!(actual, expected)
+    }
+
+    fn check_error<E: Into<ParseError>>(input: Input, expected: E) {
+        check_error_with(input, &ParseOptions::default(), expected)
+    }
+
+    fn check_error_with<E: Into<ParseError>>(mut input: Input, options: &ParseOptions, expected: E) {
+        let expected = expected.into();
+        let actual = parse_tokens(&mut input, options).unwrap_err();
 
         assert_eq!(actual, expected)
     }
 
+    #[test]
+    fn no_tokens() {
+        let input = input(vec![]);
+
+        check_error(input, TokenParseError::UnexpectedEof)
+    }
+
     #[test]
     fn parses_null() {
         let input = input(vec![Token::Null]);
@@ -184,8 +454,8 @@ mod tests {
 
     #[test]
     fn parses_number() {
-        let input = input(vec![Token::Number(14.0)]);
-        let expected = Value::Number(14.0);
+        let input = input(vec![Token::number(14.0)]);
+        let expected = Value::number(14.0);
 
         check(input, expected);
     }
@@ -206,14 +476,6 @@ mod tests {
         check(input, expected);
     }
 
-    #[test]
-    fn parses_string_unescape_backslash() {
-        let input = input(vec![Token::String(r#"hello\\world"#.into())]);
-        let expected = Value::String(r#"hello\world"#.into());
-
-        check(input, expected);
-    }
-
     #[test]
     fn parses_array_one_element() {
         let input = input(vec![Token::LeftBracket, Token::True, Token::RightBracket]);
@@ -224,8 +486,8 @@ mod tests {
 
     #[test]
     fn parses_array_two_elements() {
-        let input = input(vec![Token::LeftBracket, Token::Null, Token::Comma, Token::Number(16.0), Token::RightBracket]);
-        let expected = Value::Array(vec![Value::Null, Value::Number(16.0)]);
+        let input = input(vec![Token::LeftBracket, Token::Null, Token::Comma, Token::number(16.0), Token::RightBracket]);
+        let expected = Value::Array(vec![Value::Null, Value::number(16.0)]);
 
         check(input, expected)
     }
@@ -244,12 +506,12 @@ mod tests {
             Token::LeftBracket,
             Token::Null,
             Token::Comma, 
-            Token::Number(16.0),
+            Token::number(16.0),
             Token::Comma,
             Token::LeftBracket, 
             Token::Null,
             Token::Comma,
-            Token::Number(16.0),
+            Token::number(16.0),
             Token::RightBracket,
             Token::Comma,
             Token::Null,
@@ -258,10 +520,10 @@ mod tests {
         
         let expected = Value::Array(vec![
             Value::Null,
-            Value::Number(16.0),
+            Value::number(16.0),
             Value::Array(vec![
                 Value::Null,
-                Value::Number(16.0)
+                Value::number(16.0)
             ]),
             Value::Null
         ]);
@@ -272,7 +534,7 @@ mod tests {
     #[test]
     fn parse_empty_object() {
         let input = input(vec![Token::LeftBrace, Token::RightBrace]);
-        let expected = Value::Object(HashMap::new());
+        let expected = Value::Object(Map::new());
 
         check(input, expected)
     }
@@ -287,7 +549,7 @@ mod tests {
             Token::RightBrace]
         );
         
-        let mut map = HashMap::new();
+        let mut map = Map::new();
         map.insert(
             "ASPNETCORE_ENVIRONMENT".into(),
             Value::String("Development".into())
@@ -299,23 +561,309 @@ mod tests {
     }
 
     #[test]
-    fn parse_object_with_escaped_chars() {
+    fn unclosed_array() {
+        let input = input(vec![Token::LeftBracket]);
+
+        check_error(input, TokenParseError::UnexpectedEof)
+    }
+
+    #[test]
+    fn unclosed_array_after_value() {
+        let input = input(vec![Token::LeftBracket, Token::Null]);
+
+        check_error(input, TokenParseError::UnexpectedEof)
+    }
+
+    #[test]
+    fn unclosed_array_after_comma() {
+        let input = input(vec![Token::LeftBracket, Token::Null, Token::Comma]);
+
+        check_error(input, TokenParseError::UnexpectedEof)
+    }
+
+    #[test]
+    fn unclosed_object_after_brace() {
+        let input = input(vec![Token::LeftBrace]);
+
+        check_error(input, TokenParseError::UnexpectedEof)
+    }
+
+    #[test]
+    fn unclosed_object_after_key() {
+        let input = input(vec![Token::LeftBrace, Token::string("a")]);
+
+        check_error(input, TokenParseError::UnexpectedEof)
+    }
+
+    #[test]
+    fn unclosed_object_after_colon() {
+        let input = input(vec![Token::LeftBrace, Token::string("a"), Token::Colon]);
+
+        check_error(input, TokenParseError::UnexpectedEof)
+    }
+
+    #[test]
+    fn unclosed_object_after_value() {
+        let input = input(vec![
+            Token::LeftBrace,
+            Token::string("a"),
+            Token::Colon,
+            Token::number(1.0)]
+        );
+
+        check_error(input, TokenParseError::UnexpectedEof)
+    }
+
+    #[test]
+    fn punctuation_at_top_level() {
+        for token in [Token::Comma, Token::Colon, Token::RightBrace, Token::RightBracket] {
+            check_error(input(vec![token.clone()]), TokenParseError::UnexpectedAtTopLevel { token, location: at(0) });
+        }
+    }
+
+    #[test]
+    fn punctuation_instead_of_element() {
+        let input = input(vec![Token::LeftBracket, Token::Comma, Token::Null, Token::RightBracket]);
+
+        check_error(input, TokenParseError::UnexpectedToken { token: Token::Comma, location: at(1) })
+    }
+
+    #[test]
+    fn missing_comma_in_array() {
+        let input = input(vec![Token::LeftBracket, Token::Null, Token::Null, Token::RightBracket]);
+
+        check_error(input, TokenParseError::ExpectedComma { found: Token::Null, location: at(2) })
+    }
+
+    #[test]
+    fn missing_colon_in_object() {
+        let input = input(vec![Token::LeftBrace, Token::string("a"), Token::Null, Token::RightBrace]);
+
+        check_error(input, TokenParseError::ExpectedColon { found: Token::Null, location: at(2) })
+    }
+
+    #[test]
+    fn missing_colon_leaves_token_unconsumed() {
+        let mut input = input(vec![Token::LeftBrace, Token::string("a"), Token::string("b"), Token::Colon]);
+
+        let actual = parse_tokens(&mut input, &ParseOptions::default()).unwrap_err();
+
+        assert_eq!(actual, TokenParseError::ExpectedColon { found: Token::string("b"), location: at(2) }.into());
+        assert_eq!(input.next().unwrap().map(|spanned| spanned.token), Some(Token::string("b")));
+    }
+
+    #[test]
+    fn non_string_property() {
+        let input = input(vec![Token::LeftBrace, Token::Null, Token::Colon, Token::Null, Token::RightBrace]);
+
+        check_error(input, TokenParseError::ExpectedProperty { location: at(1) })
+    }
+
+    #[test]
+    fn surfaces_tokenizer_error() {
+        let error = TokenizeError::CharNotRecognized { ch: '?', location: at(1) };
+        let tokens = vec![
+            Ok(SpannedToken { token: Token::LeftBracket, span: Span { start: at(0), end: at(1) } }),
+            Err(error.clone()),
+        ];
+        let input = TokenStream::new(tokens.into_iter());
+
+        check_error(input, error)
+    }
+
+    #[test]
+    fn depth_within_limit() {
+        let mut input = input(vec![
+            Token::LeftBracket,
+            Token::LeftBrace,
+            Token::RightBrace,
+            Token::RightBracket]
+        );
+        let options = ParseOptions { max_depth: 2, ..ParseOptions::default() };
+
+        let actual = parse_tokens(&mut input, &options).unwrap();
+
+        assert_eq!(actual, Value::Array(vec![Value::Object(Map::new())]))
+    }
+
+    #[test]
+    fn depth_limit_exceeded() {
         let input = input(vec![
+            Token::LeftBracket,
+            Token::LeftBracket,
             Token::LeftBrace,
-            Token::String("key".to_string()),
+            Token::RightBrace,
+            Token::RightBracket,
+            Token::RightBracket]
+        );
+        let options = ParseOptions { max_depth: 2, ..ParseOptions::default() };
+
+        check_error_with(input, &options, TokenParseError::DepthLimitExceeded { location: at(2) })
+    }
+
+    #[test]
+    fn zero_depth_allows_scalars_only() {
+        let options = ParseOptions { max_depth: 0, ..ParseOptions::default() };
+
+        let actual = parse_tokens(&mut input(vec![Token::Null]), &options).unwrap();
+        assert_eq!(actual, Value::Null);
+
+        check_error_with(
+            input(vec![Token::LeftBracket, Token::RightBracket]),
+            &options,
+            TokenParseError::DepthLimitExceeded { location: at(0) },
+        )
+    }
+
+    #[test]
+    fn elements_within_limit() {
+        let options = ParseOptions { max_elements: Some(2), ..ParseOptions::default() };
+        let mut input = input(vec![
+            Token::LeftBracket,
+            Token::Null,
+            Token::Comma,
+            Token::LeftBrace,
+            Token::string("a"),
             Token::Colon,
-            Token::String("value with \\\"quotes\\\" and \\n newline".to_string()), 
+            Token::Null,
+            Token::RightBrace,
+            Token::RightBracket,
+        ]);
+
+        let actual = parse_tokens(&mut input, &options).unwrap();
+
+        assert_eq!(actual, Value::Array(vec![Value::Null, Value::object([("a", Value::Null)])]))
+    }
+
+    #[test]
+    fn too_many_array_elements() {
+        let input = input(vec![
+            Token::LeftBracket,
+            Token::Null,
+            Token::Comma,
+            Token::Null,
+            Token::Comma,
+            Token::Null,
+            Token::RightBracket,
+        ]);
+        let options = ParseOptions { max_elements: Some(2), ..ParseOptions::default() };
+
+        check_error_with(input, &options, TokenParseError::TooManyElements { location: at(5) })
+    }
+
+    #[test]
+    fn too_many_object_entries() {
+        let input = input(vec![
+            Token::LeftBrace,
+            Token::string("a"),
+            Token::Colon,
+            Token::Null,
+            Token::Comma,
+            Token::string("b"),
+            Token::Colon,
+            Token::Null,
+            Token::RightBrace,
+        ]);
+        let options = ParseOptions { max_elements: Some(1), ..ParseOptions::default() };
+
+        check_error_with(input, &options, TokenParseError::TooManyElements { location: at(5) })
+    }
+
+    #[test]
+    fn zero_elements_allows_empty_containers_only() {
+        let options = ParseOptions { max_elements: Some(0), ..ParseOptions::default() };
+
+        let actual = parse_tokens(&mut input(vec![Token::LeftBracket, Token::RightBracket]), &options).unwrap();
+        assert_eq!(actual, Value::Array(Vec::new()));
+
+        check_error_with(
+            input(vec![Token::LeftBracket, Token::Null, Token::RightBracket]),
+            &options,
+            TokenParseError::TooManyElements { location: at(1) },
+        );
+        check_error_with(input(vec![Token::LeftBracket]), &options, TokenParseError::UnexpectedEof)
+    }
+
+    #[test]
+    fn deeply_nested_array() {
+        let depth = 100_000;
+        let mut tokens = vec![Token::LeftBracket; depth];
+        tokens.extend((0..depth).map(|_| Token::RightBracket));
+
+        let options = ParseOptions { max_depth: usize::MAX, ..ParseOptions::default() };
+
+        let mut value = parse_tokens(&mut input(tokens), &options).unwrap();
+
+        // take the tree apart level by level, dropping it whole would recurse as deep as it is nested
+        let mut actual = 0;
+        while let Value::Array(mut array) = value {
+            actual += 1;
+            value = array.pop().unwrap_or(Value::Null);
+        }
+        assert_eq!(actual, depth);
+    }
+
+    #[test]
+    fn deeply_nested_unclosed_array() {
+        let input = input(vec![Token::LeftBracket; 100_000]);
+        let options = ParseOptions { max_depth: usize::MAX, ..ParseOptions::default() };
+
+        check_error_with(input, &options, TokenParseError::UnexpectedEof)
+    }
+
+    #[test]
+    fn default_depth_limit() {
+        let nested = |depth: usize| {
+            let mut tokens = vec![Token::LeftBracket; depth];
+            tokens.extend((0..depth).map(|_| Token::RightBracket));
+            input(tokens)
+        };
+
+        assert!(parse_tokens(&mut nested(128), &ParseOptions::default()).is_ok());
+        check_error(nested(129), TokenParseError::DepthLimitExceeded { location: at(128) });
+    }
+
+    #[test]
+    fn trailing_comma_in_array() {
+        let input = input(vec![Token::LeftBracket, Token::Null, Token::Comma, Token::RightBracket]);
+
+        check_error(input, TokenParseError::TrailingComma { location: at(2) })
+    }
+
+    #[test]
+    fn trailing_comma_in_object() {
+        let input = input(vec![
+            Token::LeftBrace,
+            Token::string("a"),
+            Token::Colon,
+            Token::Null,
+            Token::Comma,
             Token::RightBrace]
         );
 
-        let mut map = HashMap::new();
-        map.insert(
-            "key".to_string(), 
-            Value::String("value with \"quotes\" and \n newline".to_string())
+        check_error(input, TokenParseError::TrailingComma { location: at(4) })
+    }
+
+    #[test]
+    fn allowed_trailing_commas() {
+        let options = ParseOptions { allow_trailing_commas: true, ..ParseOptions::default() };
+        let mut input = input(vec![
+            Token::LeftBracket,
+            Token::Null,
+            Token::Comma,
+            Token::LeftBrace,
+            Token::string("a"),
+            Token::Colon,
+            Token::Null,
+            Token::Comma,
+            Token::RightBrace,
+            Token::Comma,
+            Token::RightBracket]
         );
+        let expected = Value::Array(vec![Value::Null, Value::object([("a", Value::Null)])]);
 
-        let expected = Value::Object(map);
+        let actual = parse_tokens(&mut input, &options).unwrap();
 
-        check(input, expected);
+        assert_eq!(actual, expected)
     }
-}
\ No newline at end of file
+}
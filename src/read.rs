@@ -0,0 +1,276 @@
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+use crate::tokenize::Tokenizer;
+use crate::{parse, parse_bytes, parse_tokenizer, ParseError, ParseOptions, Value};
+
+/// Reads the whole file and parses it as a single value
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<Value, ParseError> {
+    let bytes = fs::read(path)?;
+    let input = String::from_utf8(bytes).map_err(|err| ParseError::InvalidUtf8(err.utf8_error().valid_up_to()))?;
+
+    parse(input)
+}
+
+/// Parses JSON from a reader, decoding and tokenizing it as it is read
+pub fn from_reader<R: Read>(reader: R) -> Result<Value, ParseError> {
+    let mut chars = ReadChars::new(BufReader::new(reader));
+    let value = parse_tokenizer(Tokenizer::from_chars(&mut chars), &ParseOptions::default());
+
+    // a failed read looks like the end of input to the tokenizer, so it takes precedence
+    if let Some(err) = chars.error {
+        return Err(err);
+    }
+
+    value
+}
+
+/// Parses newline-delimited JSON, yielding a result for every non-blank line together
+/// with its line number, counting from 1
+///
+/// An invalid line doesn't stop the iteration, a failed read does and is reported at the line
+/// it was reading. Only the `\r` of `\r\n` line endings is removed before parsing, so locations
+/// in errors and byte offsets of [`ParseError::InvalidUtf8`] count from the start of the line.
+pub fn parse_ndjson<R: Read>(reader: R) -> impl Iterator<Item = (usize, Result<Value, ParseError>)> {
+    let mut lines = BufReader::new(reader).split(b'\n');
+    let mut number = 0;
+    let mut failed = false;
+
+    std::iter::from_fn(move || {
+        while !failed {
+            number += 1;
+            let line = match lines.next()? {
+                Ok(line) => line,
+                Err(err) => {
+                    failed = true;
+                    return Some((number, Err(err.into())));
+                }
+            };
+
+            let line = line.strip_suffix(b"\r").unwrap_or(&line);
+            if !line.trim_ascii().is_empty() {
+                return Some((number, parse_bytes(line)));
+            }
+        }
+
+        None
+    })
+}
+
+/// Iterator over UTF-8 characters of a reader, stops at the first error and keeps it
+struct ReadChars<R: BufRead> {
+    reader: R,
+    byte_offset: usize,
+    error: Option<ParseError>,
+}
+
+impl<R: BufRead> ReadChars<R> {
+    fn new(reader: R) -> Self {
+        Self { reader, byte_offset: 0, error: None }
+    }
+
+    fn read_byte(&mut self) -> io::Result<Option<u8>> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buffer) => {
+                    let Some(&byte) = buffer.first() else {
+                        return Ok(None);
+                    };
+                    self.reader.consume(1);
+                    return Ok(Some(byte));
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    fn read_char(&mut self) -> Result<Option<char>, ParseError> {
+        let start = self.byte_offset;
+        let Some(first) = self.read_byte()? else {
+            return Ok(None);
+        };
+
+        let width = match first {
+            0x00..=0x7f => 1,
+            0xc2..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf4 => 4,
+            _ => return Err(ParseError::InvalidUtf8(start)),
+        };
+
+        let mut bytes = [first, 0, 0, 0];
+        for byte in bytes.iter_mut().take(width).skip(1) {
+            *byte = self.read_byte()?.ok_or(ParseError::InvalidUtf8(start))?;
+        }
+        self.byte_offset += width;
+
+        let decoded = std::str::from_utf8(&bytes[..width]).map_err(|_| ParseError::InvalidUtf8(start))?;
+        Ok(decoded.chars().next())
+    }
+}
+
+impl<R: BufRead> Iterator for ReadChars<R> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        match self.read_char() {
+            Ok(ch) => ch,
+            Err(err) => {
+                self.error = Some(err);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::{self, Read};
+    use std::path::PathBuf;
+
+    use crate::{ParseError, Value};
+    use super::{from_reader, parse_file, parse_ndjson};
+
+    /// File in the temporary directory that is removed when dropped
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, contents: &[u8]) -> Self {
+            let path = std::env::temp_dir().join(format!("json-parser-{}-{name}", std::process::id()));
+            fs::write(&path, contents).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    /// Hands out one byte per read, so characters are split across reads
+    struct ByteByByte<'a>(&'a [u8]);
+
+    impl Read for ByteByByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let Some((&first, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+            buf[0] = first;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disconnected"))
+        }
+    }
+
+    #[test]
+    fn reads_value() {
+        let input = r#"{"name": "💩", "tags": [1, 2.5, null]}"#;
+        let expected = Value::object([
+            ("name", Value::string("💩")),
+            ("tags", Value::Array(vec![Value::number(1), Value::number(2.5), Value::Null])),
+        ]);
+
+        let actual = from_reader(ByteByByte(input.as_bytes())).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn reads_from_slice() {
+        let actual = from_reader(&b"[true]"[..]).unwrap();
+
+        assert_eq!(actual, Value::Array(vec![Value::Boolean(true)]));
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let actual = from_reader(&b"[\"ab\xff\"]"[..]).unwrap_err();
+
+        assert_eq!(actual, ParseError::InvalidUtf8(4));
+    }
+
+    #[test]
+    fn truncated_utf8() {
+        let actual = from_reader(&b"\"\xf0\x9f"[..]).unwrap_err();
+
+        assert_eq!(actual, ParseError::InvalidUtf8(1));
+    }
+
+    #[test]
+    fn ndjson_lines() {
+        let input = "{\"a\": 1}\r\n\n  \n[true] \n\"last\"";
+
+        let actual: Vec<_> = parse_ndjson(ByteByByte(input.as_bytes())).collect();
+
+        assert_eq!(actual, vec![
+            (1, Ok(Value::object([("a", Value::number(1))]))),
+            (4, Ok(Value::Array(vec![Value::Boolean(true)]))),
+            (5, Ok(Value::string("last"))),
+        ]);
+    }
+
+    #[test]
+    fn ndjson_continues_after_invalid_line() {
+        let input = &b"1\n{\"a\" 2}\n\n  \"\xff\"\n3\n"[..];
+
+        let actual: Vec<_> = parse_ndjson(input).collect();
+
+        assert_eq!(actual.len(), 4);
+        assert_eq!(actual[0], (1, Ok(Value::number(1))));
+        assert!(matches!(actual[1], (2, Err(ParseError::ParseError(_)))));
+        assert_eq!(actual[2], (4, Err(ParseError::InvalidUtf8(3))));
+        assert_eq!(actual[3], (5, Ok(Value::number(3))));
+    }
+
+    #[test]
+    fn ndjson_stops_after_io_error() {
+        let actual: Vec<_> = parse_ndjson(Failing).collect();
+
+        assert!(matches!(actual.as_slice(), [(1, Err(ParseError::Io(_)))]));
+    }
+
+    #[test]
+    fn io_error() {
+        let actual = from_reader(Failing).unwrap_err();
+
+        assert!(matches!(actual, ParseError::Io(err) if err.to_string() == "disconnected"));
+    }
+
+    #[test]
+    fn reads_file() {
+        let file = TempFile::new("reads_file.json", b"{\"ok\": [true]}\n");
+
+        let actual = parse_file(&file.0).unwrap();
+
+        assert_eq!(actual, Value::object([("ok", Value::Array(vec![Value::Boolean(true)]))]));
+    }
+
+    #[test]
+    fn file_with_invalid_utf8() {
+        let file = TempFile::new("file_with_invalid_utf8.json", b"[\"ab\xff\"]");
+
+        assert_eq!(parse_file(&file.0).unwrap_err(), ParseError::InvalidUtf8(4));
+    }
+
+    #[test]
+    fn missing_file() {
+        let actual = parse_file(std::env::temp_dir().join("json-parser-missing.json")).unwrap_err();
+
+        assert!(matches!(actual, ParseError::Io(err) if err.kind() == io::ErrorKind::NotFound));
+    }
+}
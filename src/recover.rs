@@ -0,0 +1,441 @@
+use crate::parse::{unexpected_token, TokenParseError};
+use crate::tokenize::{ByteCursor, SpannedToken, Token, Tokenizer};
+use crate::{Map, ParseError, ParseOptions, Value};
+
+/// Parses input, recovering from errors at array element and object entry boundaries
+///
+/// Returns as much of the value as could be made sense of, `None` when there is none,
+/// together with every problem found in the order it was found.
+pub fn parse_collect_errors(input: &str) -> (Option<Value>, Vec<ParseError>) {
+    parse_collect_errors_with(input, &ParseOptions::default())
+}
+
+pub fn parse_collect_errors_with(input: &str, options: &ParseOptions) -> (Option<Value>, Vec<ParseError>) {
+    let mut parser = Recovering {
+        tokens: Tokenizer::new(input).with_options(options).recovering(),
+        peeked: None,
+        errors: Vec::new(),
+        options,
+    };
+
+    if parser.peek().is_none() {
+        return (None, vec![ParseError::no_value(parser.tokens.location())]);
+    }
+
+    let value = parser.value(0);
+    if value.is_some() && parser.peek().is_some() {
+        parser.errors.push(ParseError::TrailingTokens);
+    }
+
+    (value, parser.errors)
+}
+
+/// Whether skipping stopped after a comma, so more elements follow, or the container ended
+enum Skipped {
+    Comma,
+    End,
+}
+
+/// Token, or the place of one the tokenizer failed on and whose error is already recorded
+#[derive(Clone)]
+enum Lexeme {
+    Token(SpannedToken),
+    Broken,
+}
+
+struct Recovering<'a> {
+    tokens: Tokenizer<ByteCursor<'a>>,
+    peeked: Option<Lexeme>,
+    errors: Vec<ParseError>,
+    options: &'a ParseOptions,
+}
+
+impl Recovering<'_> {
+    fn peek(&mut self) -> Option<&Lexeme> {
+        if self.peeked.is_none() {
+            self.peeked = match self.tokens.next()? {
+                Ok(token) => Some(Lexeme::Token(token)),
+                Err(err) => {
+                    self.errors.push(err.into());
+                    Some(Lexeme::Broken)
+                }
+            };
+        }
+
+        self.peeked.as_ref()
+    }
+
+    fn next(&mut self) -> Option<Lexeme> {
+        self.peek();
+        self.peeked.take()
+    }
+
+    fn peek_is(&mut self, token: &Token) -> bool {
+        matches!(self.peek(), Some(Lexeme::Token(spanned)) if spanned.token == *token)
+    }
+
+    fn peek_starts_value(&mut self) -> bool {
+        match self.peek() {
+            Some(Lexeme::Token(spanned)) => starts_value(&spanned.token),
+            Some(Lexeme::Broken) => true,
+            None => false,
+        }
+    }
+
+    fn error(&mut self, error: TokenParseError) {
+        self.errors.push(error.into());
+    }
+
+    /// Recursion is bounded by `ParseOptions::max_depth`
+    fn value(&mut self, depth: usize) -> Option<Value> {
+        let SpannedToken { token, span } = match self.next() {
+            Some(Lexeme::Token(token)) => token,
+            Some(Lexeme::Broken) => return None,
+            None => {
+                self.error(TokenParseError::UnexpectedEof);
+                return None;
+            }
+        };
+
+        if matches!(token, Token::LeftBracket | Token::LeftBrace) && depth >= self.options.max_depth {
+            self.error(TokenParseError::DepthLimitExceeded { location: span.start });
+            let close = if token == Token::LeftBracket { Token::RightBracket } else { Token::RightBrace };
+            while let Skipped::Comma = self.skip_to_boundary(&close) {}
+            return None;
+        }
+
+        match token {
+            Token::Null => Some(Value::Null),
+            Token::True => Some(Value::Boolean(true)),
+            Token::False => Some(Value::Boolean(false)),
+            Token::Number(number) => Some(Value::Number(number)),
+            Token::RawNumber(text) => Some(Value::RawNumber(text)),
+            Token::String(string) => Some(Value::String(string)),
+            Token::LeftBracket => Some(Value::Array(self.array(depth + 1))),
+            Token::LeftBrace => Some(Value::Object(self.object(depth + 1))),
+            token => {
+                self.error(unexpected_token(token, span.start, depth == 0));
+                None
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Vec<Value> {
+        let mut array = Vec::new();
+
+        loop {
+            match self.peek().cloned() {
+                None => {
+                    self.error(TokenParseError::UnexpectedEof);
+                    return array;
+                }
+                Some(Lexeme::Token(SpannedToken { token: Token::RightBracket, .. })) => {
+                    self.next();
+                    return array;
+                }
+                // a closer of the wrong kind most likely ends this array
+                Some(Lexeme::Token(SpannedToken { token: token @ Token::RightBrace, span })) => {
+                    self.next();
+                    self.error(TokenParseError::UnexpectedToken { token, location: span.start });
+                    return array;
+                }
+                Some(Lexeme::Token(SpannedToken { token, span })) if !starts_value(&token) => {
+                    self.next();
+                    self.error(TokenParseError::UnexpectedToken { token, location: span.start });
+                    continue;
+                }
+                Some(_) => array.extend(self.value(depth)),
+            }
+
+            match self.after_element(&Token::RightBracket) {
+                Skipped::Comma => {}
+                Skipped::End => return array,
+            }
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Map {
+        let mut map = Map::new();
+
+        loop {
+            let key = match self.next() {
+                None => {
+                    self.error(TokenParseError::UnexpectedEof);
+                    return map;
+                }
+                Some(Lexeme::Token(SpannedToken { token: Token::RightBrace, .. })) => return map,
+                Some(Lexeme::Token(SpannedToken { token: Token::String(key), .. })) => Some(key),
+                // the error is already recorded, the entry is parsed but dropped
+                Some(Lexeme::Broken) => None,
+                Some(Lexeme::Token(SpannedToken { token, span })) => {
+                    self.error(TokenParseError::ExpectedProperty { location: span.start });
+                    let skipped = match token {
+                        Token::Comma => Skipped::Comma,
+                        // a container used as a key is skipped as a whole along with the rest of the entry
+                        Token::LeftBracket => self.skip_container(&Token::RightBracket),
+                        Token::LeftBrace => self.skip_container(&Token::RightBrace),
+                        _ => self.skip_to_boundary(&Token::RightBrace),
+                    };
+                    match skipped {
+                        Skipped::Comma => continue,
+                        Skipped::End => return map,
+                    }
+                }
+            };
+
+            if self.peek_is(&Token::Colon) {
+                self.next();
+            } else {
+                match self.peek() {
+                    Some(Lexeme::Token(SpannedToken { token, span })) => {
+                        let error = TokenParseError::ExpectedColon { found: token.clone(), location: span.start };
+                        self.error(error);
+                    }
+                    Some(Lexeme::Broken) => {}
+                    None => self.error(TokenParseError::UnexpectedEof),
+                }
+                if !self.peek_starts_value() {
+                    match self.skip_to_boundary(&Token::RightBrace) {
+                        Skipped::Comma => continue,
+                        Skipped::End => return map,
+                    }
+                }
+            }
+
+            match self.peek().cloned() {
+                Some(Lexeme::Token(SpannedToken { token, span })) if !starts_value(&token) => {
+                    self.error(TokenParseError::UnexpectedToken { token, location: span.start });
+                }
+                _ => {
+                    let value = self.value(depth);
+                    if let (Some(key), Some(value)) = (key, value) {
+                        map.insert(key, value);
+                    }
+                }
+            }
+
+            match self.after_element(&Token::RightBrace) {
+                Skipped::Comma => {}
+                Skipped::End => return map,
+            }
+        }
+    }
+
+    /// Consumes what follows an array element or an object entry
+    fn after_element(&mut self, close: &Token) -> Skipped {
+        let SpannedToken { token, span } = match self.peek().cloned() {
+            Some(Lexeme::Token(token)) => token,
+            // whatever broke is most likely the next element
+            Some(Lexeme::Broken) => return Skipped::Comma,
+            None => {
+                self.error(TokenParseError::UnexpectedEof);
+                return Skipped::End;
+            }
+        };
+
+        match token {
+            Token::Comma => {
+                self.next();
+                if self.peek_is(close) {
+                    if !self.options.allow_trailing_commas {
+                        self.error(TokenParseError::TrailingComma { location: span.start });
+                    }
+                    self.next();
+                    return Skipped::End;
+                }
+                Skipped::Comma
+            }
+            token if token == *close => {
+                self.next();
+                Skipped::End
+            }
+            // a closer of the wrong kind most likely ends this container
+            Token::RightBracket | Token::RightBrace => {
+                self.next();
+                self.error(TokenParseError::UnexpectedToken { token, location: span.start });
+                Skipped::End
+            }
+            // most likely a forgotten comma, the element is parsed as if it was there
+            found if starts_value(&found) => {
+                self.error(TokenParseError::ExpectedComma { found, location: span.start });
+                Skipped::Comma
+            }
+            found => {
+                self.error(TokenParseError::ExpectedComma { found, location: span.start });
+                self.skip_to_boundary(close)
+            }
+        }
+    }
+
+    /// Skips the rest of a container whose opening token was consumed, then the rest of the element
+    fn skip_container(&mut self, close: &Token) -> Skipped {
+        while let Skipped::Comma = self.skip_to_boundary(close) {}
+        match self.peek() {
+            None => Skipped::End,
+            Some(_) => self.skip_to_boundary(&Token::RightBrace),
+        }
+    }
+
+    /// Skips tokens up to a comma or the closer of the current container, both consumed,
+    /// nested containers are skipped as a whole
+    fn skip_to_boundary(&mut self, close: &Token) -> Skipped {
+        let mut nesting = 0usize;
+
+        while let Some(lexeme) = self.next() {
+            let Lexeme::Token(SpannedToken { token, .. }) = lexeme else {
+                continue;
+            };
+            match token {
+                Token::LeftBracket | Token::LeftBrace => nesting += 1,
+                Token::RightBracket | Token::RightBrace if nesting > 0 => nesting -= 1,
+                Token::Comma if nesting == 0 => return Skipped::Comma,
+                token if nesting == 0 && token == *close => return Skipped::End,
+                _ => {}
+            }
+        }
+
+        self.error(TokenParseError::UnexpectedEof);
+        Skipped::End
+    }
+}
+
+fn starts_value(token: &Token) -> bool {
+    !matches!(
+        token,
+        Token::Comma | Token::Colon | Token::RightBracket | Token::RightBrace
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::TokenParseError;
+    use crate::tokenize::{Token, TokenizeError};
+    use crate::{parse, Location, ParseError, ParseOptions, Value};
+    use super::{parse_collect_errors, parse_collect_errors_with};
+
+    fn errors(input: &str) -> Vec<ParseError> {
+        parse_collect_errors(input).1
+    }
+
+    #[test]
+    fn valid_input_has_no_errors() {
+        let input = r#"{"a": [1, {"b": null}], "c": "d"}"#;
+
+        let (value, errors) = parse_collect_errors(input);
+
+        assert_eq!(value, Some(parse(String::from(input)).unwrap()));
+        assert_eq!(errors, vec![]);
+    }
+
+    #[test]
+    fn reports_every_broken_array_element() {
+        let (value, errors) = parse_collect_errors("[1, 01, 2, \"\\q\", 3 4, ]");
+
+        assert_eq!(value, Some(Value::Array(vec![Value::number(1), Value::number(2), Value::number(3), Value::number(4)])));
+        assert_eq!(errors, vec![
+            ParseError::TokenizeError(TokenizeError::InvalidNumber {
+                message: "Invalid number provided.".to_string(),
+                location: Location::new(1, 5, 4),
+            }),
+            ParseError::TokenizeError(TokenizeError::InvalidEscape { ch: 'q', location: Location::new(1, 13, 12) }),
+            TokenParseError::ExpectedComma { found: Token::number(4), location: Location::new(1, 20, 19) }.into(),
+            TokenParseError::TrailingComma { location: Location::new(1, 21, 20) }.into(),
+        ]);
+    }
+
+    #[test]
+    fn recovers_at_object_entries() {
+        let (value, errors) = parse_collect_errors(r#"{"a" 1, 2: 3, "b": [1,, 2], "c": }"#);
+
+        assert_eq!(value, Some(Value::object([
+            ("a", Value::number(1)),
+            ("b", Value::Array(vec![Value::number(1), Value::number(2)])),
+        ])));
+        assert_eq!(errors, vec![
+            TokenParseError::ExpectedColon { found: Token::number(1), location: Location::new(1, 6, 5) }.into(),
+            TokenParseError::ExpectedProperty { location: Location::new(1, 9, 8) }.into(),
+            TokenParseError::UnexpectedToken { token: Token::Comma, location: Location::new(1, 23, 22) }.into(),
+            TokenParseError::UnexpectedToken { token: Token::RightBrace, location: Location::new(1, 34, 33) }.into(),
+        ]);
+    }
+
+    #[test]
+    fn drops_entries_with_broken_tokens() {
+        let (value, errors) = parse_collect_errors(r#"{"\q": 1, "a": 01, "b": 2}"#);
+
+        assert_eq!(value, Some(Value::object([("b", Value::number(2))])));
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], ParseError::TokenizeError(TokenizeError::InvalidEscape { .. })));
+        assert!(matches!(errors[1], ParseError::TokenizeError(TokenizeError::InvalidNumber { .. })));
+    }
+
+    #[test]
+    fn short_unicode_escape_keeps_closing_quote() {
+        let (value, errors) = parse_collect_errors(r#"["\u12", 1, "\x"]"#);
+
+        assert_eq!(value, Some(Value::Array(vec![Value::number(1)])));
+        assert_eq!(errors, vec![
+            ParseError::TokenizeError(TokenizeError::InvalidHexValue { location: Location::new(1, 3, 2) }),
+            ParseError::TokenizeError(TokenizeError::InvalidEscape { ch: 'x', location: Location::new(1, 14, 13) }),
+        ]);
+    }
+
+    #[test]
+    fn broken_single_quoted_string_ends_at_its_quote() {
+        let options = ParseOptions { allow_single_quotes: true, ..ParseOptions::default() };
+
+        let (value, errors) = parse_collect_errors_with(r#"['\q', 1, 'a"b', 2]"#, &options);
+
+        assert_eq!(value, Some(Value::Array(vec![Value::number(1), Value::string("a\"b"), Value::number(2)])));
+        assert_eq!(errors, vec![ParseError::TokenizeError(TokenizeError::InvalidEscape { ch: 'q', location: Location::new(1, 3, 2) })]);
+    }
+
+    #[test]
+    fn skips_nested_containers_in_broken_entries() {
+        let (value, errors) = parse_collect_errors(r#"{[1, {"x": 2}]: 3, "a": 1}"#);
+
+        assert_eq!(value, Some(Value::object([("a", Value::number(1))])));
+        assert_eq!(errors, vec![TokenParseError::ExpectedProperty { location: Location::new(1, 2, 1) }.into()]);
+    }
+
+    #[test]
+    fn unclosed_containers() {
+        let (value, errors) = parse_collect_errors(r#"{"a": [1, 2"#);
+
+        assert_eq!(value, Some(Value::object([("a", Value::Array(vec![Value::number(1), Value::number(2)]))])));
+        assert_eq!(errors, vec![TokenParseError::UnexpectedEof.into(), TokenParseError::UnexpectedEof.into()]);
+    }
+
+    #[test]
+    fn mismatched_closer() {
+        let (value, errors) = parse_collect_errors("[[1}]");
+
+        assert_eq!(value, Some(Value::Array(vec![Value::Array(vec![Value::number(1)])])));
+        assert_eq!(errors, vec![TokenParseError::UnexpectedToken {
+            token: Token::RightBrace,
+            location: Location::new(1, 4, 3),
+        }.into()]);
+    }
+
+    #[test]
+    fn no_value() {
+        assert_eq!(parse_collect_errors(""), (None, vec![ParseError::EmptyInput]));
+        assert_eq!(parse_collect_errors(" \n"), (None, vec![ParseError::BlankInput]));
+        assert_eq!(
+            parse_collect_errors("]"),
+            (None, vec![TokenParseError::UnexpectedAtTopLevel { token: Token::RightBracket, location: Location::new(1, 1, 0) }.into()]),
+        );
+    }
+
+    #[test]
+    fn trailing_tokens() {
+        assert_eq!(parse_collect_errors("1 2"), (Some(Value::number(1)), vec![ParseError::TrailingTokens]));
+    }
+
+    #[test]
+    fn unpaired_surrogate_at_end_of_string() {
+        assert_eq!(errors(r#"["\ud83d", "ok"]"#), vec![ParseError::TokenizeError(TokenizeError::UnpairedSurrogate {
+            location: Location::new(1, 3, 2),
+        })]);
+    }
+}
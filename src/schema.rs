@@ -0,0 +1,195 @@
+use std::{error::Error, fmt};
+
+use crate::access::with_token;
+use crate::Value;
+
+/// Way a value breaks its schema, each one holds the JSON Pointer of the offending value
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaError {
+    /// Value is not of the `type` of the schema, holds the type, or types joined by `or`, it should be
+    TypeMismatch { pointer: String, expected: String, found: &'static str },
+    /// Object lacks a property listed in `required`
+    MissingProperty { pointer: String, property: String },
+    /// Value is none of the values listed in `enum`
+    NotInEnum { pointer: String },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TypeMismatch { pointer, expected, found } => {
+                write!(f, "expected {expected}, found {found} at {}", Place(pointer))
+            }
+            Self::MissingProperty { pointer, property } => {
+                write!(f, "missing required property {property:?} at {}", Place(pointer))
+            }
+            Self::NotInEnum { pointer } => write!(f, "value at {} is not one of the allowed values", Place(pointer)),
+        }
+    }
+}
+
+impl Error for SchemaError {}
+
+/// Pointer as it reads in a message, the empty pointer refers to the whole document
+struct Place<'a>(&'a str);
+
+impl fmt::Display for Place<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            f.write_str("the document root")
+        } else {
+            f.write_str(self.0)
+        }
+    }
+}
+
+/// Checks value against a JSON Schema, collecting every violation
+///
+/// Only `type`, `required`, `properties`, `items` and `enum` are supported, other keywords
+/// and keywords whose value is malformed are ignored. A schema that is not an object accepts anything.
+pub fn validate_schema(value: &Value, schema: &Value) -> Result<(), Vec<SchemaError>> {
+    let mut errors = Vec::new();
+    check(value, schema, &mut String::new(), &mut errors);
+
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+/// Checks a value whose pointer is `pointer`, which is restored before returning
+fn check(value: &Value, schema: &Value, pointer: &mut String, errors: &mut Vec<SchemaError>) {
+    if let Some(expected) = schema.get("type")
+        && let Some(types) = type_names(expected)
+        && !types.iter().any(|name| has_type(value, name))
+    {
+        let expected = types.join(" or ");
+        errors.push(SchemaError::TypeMismatch { pointer: pointer.clone(), expected, found: value.type_name() });
+        // the other keywords would only report the same mismatch again
+        return;
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum")
+        && !allowed.contains(value)
+    {
+        errors.push(SchemaError::NotInEnum { pointer: pointer.clone() });
+    }
+
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                let missing = required.iter().filter_map(Value::as_str).filter(|property| !map.contains_key(property));
+                for property in missing {
+                    errors.push(SchemaError::MissingProperty { pointer: pointer.clone(), property: property.to_owned() });
+                }
+            }
+
+            if let Some(Value::Object(properties)) = schema.get("properties") {
+                for (name, property_schema) in properties {
+                    if let Some(property) = map.get(name) {
+                        with_token(pointer, name, |pointer| check(property, property_schema, pointer, errors));
+                    }
+                }
+            }
+        }
+        Value::Array(array) => {
+            if let Some(items) = schema.get("items") {
+                for (index, item) in array.iter().enumerate() {
+                    with_token(pointer, &index.to_string(), |pointer| check(item, items, pointer, errors));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Type names of a `type` keyword, which holds one name or an array of them
+fn type_names(expected: &Value) -> Option<Vec<&str>> {
+    match expected {
+        Value::String(name) => Some(vec![name.as_str()]),
+        Value::Array(names) => names.iter().map(Value::as_str).collect(),
+        _ => None,
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        // `1.0` is an integer as well
+        "integer" => value.as_f64().is_some_and(|f| f.fract() == 0.0),
+        name => value.type_name() == name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, Value};
+    use super::{validate_schema, SchemaError};
+
+    fn json(input: &str) -> Value {
+        parse(String::from(input)).unwrap()
+    }
+
+    fn schema() -> Value {
+        json(r#"{
+            "type": "object",
+            "required": ["id", "name"],
+            "properties": {
+                "id": {"type": "integer"},
+                "name": {"type": "string"},
+                "role": {"enum": ["admin", "user"]},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "a/b": {"type": ["number", "null"]}
+            }
+        }"#)
+    }
+
+    #[test]
+    fn valid_document() {
+        let value = json(r#"{"id": 1.0, "name": "x", "role": "user", "tags": ["a"], "a/b": null, "extra": 1}"#);
+
+        assert_eq!(validate_schema(&value, &schema()), Ok(()));
+    }
+
+    #[test]
+    fn collects_every_violation() {
+        let value = json(r#"{"id": 1.5, "role": "root", "tags": ["a", 2, false], "a/b": "x"}"#);
+
+        let actual = validate_schema(&value, &schema()).unwrap_err();
+
+        assert_eq!(actual, vec![
+            SchemaError::MissingProperty { pointer: String::new(), property: String::from("name") },
+            SchemaError::TypeMismatch { pointer: String::from("/id"), expected: String::from("integer"), found: "number" },
+            SchemaError::NotInEnum { pointer: String::from("/role") },
+            SchemaError::TypeMismatch { pointer: String::from("/tags/1"), expected: String::from("string"), found: "number" },
+            SchemaError::TypeMismatch { pointer: String::from("/tags/2"), expected: String::from("string"), found: "boolean" },
+            SchemaError::TypeMismatch {
+                pointer: String::from("/a~1b"),
+                expected: String::from("number or null"),
+                found: "string",
+            },
+        ]);
+    }
+
+    #[test]
+    fn type_mismatch_skips_other_keywords() {
+        let actual = validate_schema(&json("[1]"), &schema()).unwrap_err();
+
+        assert_eq!(actual, vec![SchemaError::TypeMismatch {
+            pointer: String::new(),
+            expected: String::from("object"),
+            found: "array",
+        }]);
+    }
+
+    #[test]
+    fn unsupported_schemas_accept_anything() {
+        assert_eq!(validate_schema(&json("1"), &json("true")), Ok(()));
+        assert_eq!(validate_schema(&json("1"), &json(r#"{"type": 5, "minimum": 2}"#)), Ok(()));
+    }
+
+    #[test]
+    fn display() {
+        let error = SchemaError::TypeMismatch { pointer: String::from("/id"), expected: String::from("string"), found: "number" };
+        let missing = SchemaError::MissingProperty { pointer: String::new(), property: String::from("id") };
+
+        assert_eq!(error.to_string(), "expected string, found number at /id");
+        assert_eq!(missing.to_string(), "missing required property \"id\" at the document root");
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use crate::{Map, Number, Value};
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Null => serializer.serialize_unit(),
+            Value::Boolean(boolean) => serializer.serialize_bool(*boolean),
+            Value::Number(number) => number.serialize(serializer),
+            Value::RawNumber(text) => serialize_raw_number(text, serializer),
+            Value::String(string) => serializer.serialize_str(string),
+            Value::Array(array) => {
+                let mut seq = serializer.serialize_seq(Some(array.len()))?;
+                for value in array {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            Value::Object(map) => {
+                let mut object = serializer.serialize_map(Some(map.len()))?;
+                for (key, value) in map {
+                    object.serialize_entry(key, value)?;
+                }
+                object.end()
+            }
+        }
+    }
+}
+
+/// Serializes the text as the narrowest type that holds it, like the tokenizer would parse it
+fn serialize_raw_number<S: Serializer>(text: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    if let Ok(u) = text.parse::<u64>() {
+        serializer.serialize_u64(u)
+    } else if let Ok(i) = text.parse::<i64>() {
+        serializer.serialize_i64(i)
+    } else if let Ok(u) = text.parse::<u128>() {
+        serializer.serialize_u128(u)
+    } else if let Ok(i) = text.parse::<i128>() {
+        serializer.serialize_i128(i)
+    } else {
+        let float = text.parse::<f64>().map_err(serde::ser::Error::custom)?;
+        serializer.serialize_f64(float)
+    }
+}
+
+impl Serialize for Number {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let Some(u) = self.as_u64() {
+            serializer.serialize_u64(u)
+        } else if let Some(i) = self.as_i64() {
+            serializer.serialize_i64(i)
+        } else if let Some(u) = self.as_u128() {
+            serializer.serialize_u128(u)
+        } else if let Some(i) = self.as_i128() {
+            serializer.serialize_i128(i)
+        } else {
+            serializer.serialize_f64(self.as_f64())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_bool<E>(self, boolean: bool) -> Result<Value, E> {
+        Ok(Value::Boolean(boolean))
+    }
+
+    fn visit_i64<E>(self, i: i64) -> Result<Value, E> {
+        Ok(Value::Number(Number::from(i)))
+    }
+
+    fn visit_u64<E>(self, u: u64) -> Result<Value, E> {
+        Ok(Value::Number(Number::from(u)))
+    }
+
+    fn visit_i128<E>(self, i: i128) -> Result<Value, E> {
+        Ok(Value::Number(Number::from(i)))
+    }
+
+    fn visit_u128<E>(self, u: u128) -> Result<Value, E> {
+        Ok(Value::Number(Number::from(u)))
+    }
+
+    fn visit_f64<E>(self, f: f64) -> Result<Value, E> {
+        Ok(Value::Number(Number::from(f)))
+    }
+
+    fn visit_str<E>(self, string: &str) -> Result<Value, E> {
+        Ok(Value::String(String::from(string)))
+    }
+
+    fn visit_string<E>(self, string: String) -> Result<Value, E> {
+        Ok(Value::String(string))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut array = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            array.push(value);
+        }
+        Ok(Value::Array(array))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Value, A::Error> {
+        let mut map = Map::new();
+        while let Some((key, value)) = access.next_entry()? {
+            map.insert(key, value);
+        }
+        Ok(Value::Object(map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, Value};
+
+    #[test]
+    fn serializes_through_serde() {
+        let value = parse(String::from(r#"{"b": [1, -2, 0.5], "a": {"ok": true, "none": null}}"#)).unwrap();
+
+        let actual = serde_json::to_string(&value).unwrap();
+
+        assert_eq!(actual, r#"{"b":[1,-2,0.5],"a":{"ok":true,"none":null}}"#);
+    }
+
+    #[test]
+    fn serializes_raw_numbers() {
+        let value = Value::Array(vec![
+            Value::RawNumber(String::from("18446744073709551615")),
+            Value::RawNumber(String::from("-3")),
+            Value::RawNumber(String::from("1.10")),
+            Value::RawNumber(String::from("1267650600228229401496703205376")),
+            Value::RawNumber(String::from("-1267650600228229401496703205376")),
+        ]);
+
+        let actual = serde_json::to_string(&value).unwrap();
+
+        assert_eq!(actual, "[18446744073709551615,-3,1.1,1267650600228229401496703205376,-1267650600228229401496703205376]");
+    }
+
+    #[test]
+    fn serializes_wide_integers() {
+        let value = Value::Array(vec![Value::number(u128::MAX), Value::number(i128::MIN)]);
+
+        let actual = serde_json::to_string(&value).unwrap();
+
+        assert_eq!(actual, format!("[{},{}]", u128::MAX, i128::MIN));
+    }
+
+    #[test]
+    fn deserializes_through_serde() {
+        let input = r#"{"id": 18446744073709551615, "tags": ["admin"], "ratio": 1.5}"#;
+
+        let actual: Value = serde_json::from_str(input).unwrap();
+
+        assert_eq!(actual, parse(String::from(input)).unwrap());
+    }
+}
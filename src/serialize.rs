@@ -0,0 +1,455 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+use crate::{Number, SerializeOptions, Value};
+
+/// Indentation used for every nesting level of pretty-printed output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    /// Given amount of spaces per level
+    Spaces(usize),
+    /// One tab character per level
+    Tabs,
+}
+
+impl From<usize> for Indent {
+    fn from(spaces: usize) -> Self {
+        Self::Spaces(spaces)
+    }
+}
+
+impl Value {
+    /// Serializes value into multi-line JSON, indenting nested containers cumulatively
+    pub fn to_string_pretty(&self, indent: impl Into<Indent>) -> String {
+        let mut output = String::new();
+        let mut serializer = Serializer::new(&mut output, Some(indent.into()));
+        serializer
+            .write_value(self)
+            .expect("writing to a String never fails");
+
+        output
+    }
+
+    /// Serializes value into compact JSON according to the given options
+    pub fn to_string_with(&self, options: &SerializeOptions) -> String {
+        let mut output = String::new();
+        let mut serializer = Serializer::new(&mut output, None);
+        serializer.options = options.clone();
+        serializer
+            .write_value(self)
+            .expect("writing to a String never fails");
+
+        output
+    }
+
+    /// Serializes value into deterministic compact JSON, suitable for hashing and signing
+    ///
+    /// Object keys are sorted by code point and whole numbers are written without a fraction,
+    /// so values that compare equal produce the same output.
+    pub fn to_string_canonical(&self) -> String {
+        let mut output = String::new();
+        let mut serializer = Serializer::new(&mut output, None);
+        serializer.canonical = true;
+        serializer
+            .write_value(self)
+            .expect("writing to a String never fails");
+
+        output
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Serializer::new(f, None).write_value(self)
+    }
+}
+
+struct Serializer<W: Write> {
+    output: W,
+    indent: Option<Indent>,
+    options: SerializeOptions,
+    /// Sorts object keys and normalizes numbers
+    canonical: bool,
+    depth: usize,
+}
+
+impl<W: Write> Serializer<W> {
+    fn new(output: W, indent: Option<Indent>) -> Self {
+        Self {
+            output,
+            indent,
+            options: SerializeOptions::default(),
+            canonical: false,
+            depth: 0,
+        }
+    }
+
+    fn write_value(&mut self, value: &Value) -> fmt::Result {
+        match value {
+            Value::Null => self.output.write_str("null"),
+            Value::Boolean(boolean) => write!(self.output, "{boolean}"),
+            Value::Number(number) => self.write_number(*number),
+            Value::RawNumber(text) => self.output.write_str(text),
+            Value::String(string) => self.write_string(string),
+            Value::Array(array) if array.is_empty() => self.output.write_str("[]"),
+            Value::Array(array) => {
+                self.begin_container('[')?;
+                for (i, value) in array.iter().enumerate() {
+                    self.write_separator(i)?;
+                    self.write_value(value)?;
+                }
+                self.end_container(']')
+            }
+            Value::Object(map) if map.is_empty() => self.output.write_str("{}"),
+            Value::Object(map) => {
+                self.begin_container('{')?;
+                let mut entries: Vec<_> = map.iter().collect();
+                if self.canonical {
+                    // byte order of UTF-8 strings is the same as their code point order
+                    entries.sort_by_key(|&(key, _)| key);
+                }
+                for (i, (key, value)) in entries.into_iter().enumerate() {
+                    self.write_separator(i)?;
+                    self.write_string(key)?;
+                    self.output.write_char(':')?;
+                    if self.indent.is_some() {
+                        self.output.write_char(' ')?;
+                    }
+                    self.write_value(value)?;
+                }
+                self.end_container('}')
+            }
+        }
+    }
+
+    fn begin_container(&mut self, open: char) -> fmt::Result {
+        self.depth += 1;
+        self.output.write_char(open)
+    }
+
+    fn end_container(&mut self, close: char) -> fmt::Result {
+        self.depth -= 1;
+        self.write_newline()?;
+        self.output.write_char(close)
+    }
+
+    fn write_separator(&mut self, index: usize) -> fmt::Result {
+        if index > 0 {
+            self.output.write_char(',')?;
+        }
+        self.write_newline()
+    }
+
+    fn write_newline(&mut self) -> fmt::Result {
+        let Some(indent) = self.indent else {
+            return Ok(());
+        };
+
+        self.output.write_char('\n')?;
+        for _ in 0..self.depth {
+            match indent {
+                Indent::Spaces(spaces) => {
+                    for _ in 0..spaces {
+                        self.output.write_char(' ')?;
+                    }
+                }
+                Indent::Tabs => self.output.write_char('\t')?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_number(&mut self, number: Number) -> fmt::Result {
+        // JSON has no representation for `NaN` and infinities
+        if !number.as_f64().is_finite() {
+            return self.output.write_str("null");
+        }
+
+        // `-0.0` equals `0`
+        if self.canonical && number.is_f64() && number.as_f64() == 0.0 {
+            return self.output.write_char('0');
+        }
+
+        if let Some(precision) = self.options.float_precision
+            && number.is_f64()
+        {
+            let text = format!("{:.precision$}", number.as_f64());
+            let text = if text.contains('.') { text.trim_end_matches('0').trim_end_matches('.') } else { &text };
+            // rounding may leave nothing but the sign of a tiny negative float
+            return self.output.write_str(if text == "-0" { "0" } else { text });
+        }
+
+        write!(self.output, "{number}")
+    }
+
+    fn write_string(&mut self, string: &str) -> fmt::Result {
+        self.output.write_char('"')?;
+
+        for ch in string.chars() {
+            match ch {
+                '"' => self.output.write_str("\\\"")?,
+                '\\' => self.output.write_str("\\\\")?,
+                '/' if self.options.escape_forward_slashes => self.output.write_str("\\/")?,
+                '\n' => self.output.write_str("\\n")?,
+                '\r' => self.output.write_str("\\r")?,
+                '\t' => self.output.write_str("\\t")?,
+                '\u{8}' => self.output.write_str("\\b")?,
+                '\u{c}' => self.output.write_str("\\f")?,
+                // the rest of the control characters have no short escape
+                ch if (ch as u32) < 0x20 => write!(self.output, "\\u{:04x}", ch as u32)?,
+                ch if self.options.ascii_only && !ch.is_ascii() => {
+                    let mut units = [0; 2];
+                    for unit in ch.encode_utf16(&mut units) {
+                        write!(self.output, "\\u{unit:04x}")?;
+                    }
+                }
+                ch => self.output.write_char(ch)?,
+            }
+        }
+
+        self.output.write_char('"')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, Indent, SerializeOptions, Value};
+
+    fn check(value: Value, expected: &str) {
+        let actual = value.to_string();
+
+        assert_eq!(actual, expected)
+    }
+
+    fn check_pretty(value: Value, indent: Indent, expected: &str) {
+        let actual = value.to_string_pretty(indent);
+
+        assert_eq!(actual, expected)
+    }
+
+    fn check_round_trip(input: &str) {
+        let value = parse(String::from(input)).unwrap();
+        let actual = parse(value.to_string()).unwrap();
+
+        assert_eq!(actual, value)
+    }
+
+    #[test]
+    fn serializes_literals() {
+        check(Value::Null, "null");
+        check(Value::Boolean(true), "true");
+        check(Value::Boolean(false), "false");
+    }
+
+    #[test]
+    fn serializes_numbers() {
+        check(Value::number(1), "1");
+        check(Value::number(1.0), "1");
+        check(Value::number(1415436218769.0), "1415436218769");
+        check(Value::number(2.5e-3), "0.0025");
+        check(Value::number(-0.88), "-0.88");
+        check(Value::number(1415436218769u64), "1415436218769");
+    }
+
+    #[test]
+    fn serializes_non_finite_number_as_null() {
+        check(Value::number(f64::NAN), "null");
+        check(Value::number(f64::INFINITY), "null");
+    }
+
+    #[test]
+    fn serializes_escaped_string() {
+        check(
+            Value::string("quote \" backslash \\ newline \n tab \t bell \u{7}"),
+            r#""quote \" backslash \\ newline \n tab \t bell \u0007""#,
+        );
+    }
+
+    #[test]
+    fn every_escape_round_trips() {
+        let input = r#""\"\\\/\b\f\n\r\t\u0041\u00e9\ud83d\ude00\u001f""#;
+        let value = parse(String::from(input)).unwrap();
+
+        assert_eq!(value, Value::string("\"\\/\u{8}\u{c}\n\r\tAé\u{1F600}\u{1f}"));
+        // short escapes are preferred over `\u`, and `\/` is only written when asked for
+        let written = value.to_string();
+        assert_eq!(written, r#""\"\\/\b\f\n\r\tAé😀\u001f""#);
+        assert_eq!(parse(written).unwrap(), value);
+
+        let options = SerializeOptions { escape_forward_slashes: true, ascii_only: true, ..SerializeOptions::default() };
+        let written = value.to_string_with(&options);
+        assert_eq!(written, r#""\"\\\/\b\f\n\r\tA\u00e9\ud83d\ude00\u001f""#);
+        assert_eq!(parse(written).unwrap(), value);
+    }
+
+    #[test]
+    fn nul_round_trips() {
+        let value = parse(String::from(r#""a\u0000b""#)).unwrap();
+
+        assert_eq!(value, Value::string("a\0b"));
+        assert_eq!(value.to_string(), r#""a\u0000b""#);
+    }
+
+    #[test]
+    fn serializes_empty_containers() {
+        check(Value::Array(vec![]), "[]");
+        check(Value::object([]), "{}");
+    }
+
+    #[test]
+    fn serializes_array() {
+        check(
+            Value::Array(vec![Value::Null, Value::number(16), Value::string("a")]),
+            r#"[null,16,"a"]"#,
+        );
+    }
+
+    #[test]
+    fn serializes_object() {
+        check(
+            Value::object([("key", Value::Array(vec![Value::Boolean(true)]))]),
+            r#"{"key":[true]}"#,
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        check_round_trip(r#"{"user": {"id": 1415436218769, "tags": ["admin", "ru"]}}"#);
+        check_round_trip(r#"[0.5e2, -0.88, 18446744073709551615, -9223372036854775808, "with \\ backslash and \n newline", {}, []]"#);
+    }
+
+    #[test]
+    fn ascii_only_escapes_non_ascii() {
+        let options = SerializeOptions { ascii_only: true, ..SerializeOptions::default() };
+        let value = Value::object([("ключ", Value::string("é a"))]);
+
+        let actual = value.to_string_with(&options);
+
+        assert_eq!(actual, r#"{"\u043a\u043b\u044e\u0447":"\u00e9 a"}"#);
+        assert_eq!(parse(actual).unwrap(), value);
+    }
+
+    #[test]
+    fn ascii_only_writes_surrogate_pairs() {
+        let options = SerializeOptions { ascii_only: true, ..SerializeOptions::default() };
+
+        let actual = Value::string("\u{1F600}").to_string_with(&options);
+
+        assert_eq!(actual, r#""\ud83d\ude00""#);
+        assert_eq!(parse(actual).unwrap(), Value::string("\u{1F600}"));
+    }
+
+    #[test]
+    fn escapes_forward_slashes() {
+        let options = SerializeOptions { escape_forward_slashes: true, ..SerializeOptions::default() };
+        let value = Value::object([("a/b", Value::string("</script>"))]);
+
+        let actual = value.to_string_with(&options);
+
+        assert_eq!(actual, r#"{"a\/b":"<\/script>"}"#);
+        assert_eq!(parse(actual).unwrap(), value);
+        assert_eq!(value.to_string_with(&SerializeOptions::default()), r#"{"a/b":"</script>"}"#);
+    }
+
+    #[test]
+    fn float_precision() {
+        let options = SerializeOptions { float_precision: Some(3), ..SerializeOptions::default() };
+        let value = Value::Array(vec![
+            Value::number(1.23456),
+            Value::number(2.5),
+            Value::number(1.9999),
+            Value::number(-0.0001),
+            Value::number(123456789),
+        ]);
+
+        assert_eq!(value.to_string_with(&options), "[1.235,2.5,2,0,123456789]");
+        assert_eq!(Value::number(0.1).to_string_with(&SerializeOptions { float_precision: Some(0), ..options }), "0");
+    }
+
+    #[test]
+    fn default_precision_round_trips() {
+        let value = Value::Array(vec![Value::number(0.1 + 0.2), Value::number(1e-7), Value::number(f64::MAX)]);
+
+        assert_eq!(parse(value.to_string_with(&SerializeOptions::default())).unwrap(), value);
+    }
+
+    #[test]
+    fn default_options_write_utf8() {
+        let value = Value::string("é \u{1F600}");
+
+        assert_eq!(value.to_string_with(&SerializeOptions::default()), "\"é \u{1F600}\"");
+    }
+
+    #[test]
+    fn canonical_sorts_keys() {
+        let value = Value::object([
+            ("b", Value::number(1)),
+            ("a", Value::object([("é", Value::Null), ("z", Value::Null), ("B", Value::Null)])),
+            ("\u{1F600}", Value::Null),
+            ("\u{FF61}", Value::Null),
+        ]);
+
+        assert_eq!(
+            value.to_string_canonical(),
+            "{\"a\":{\"B\":null,\"z\":null,\"é\":null},\"b\":1,\"\u{FF61}\":null,\"\u{1F600}\":null}",
+        );
+    }
+
+    #[test]
+    fn canonical_numbers() {
+        let canonical = |value: Value| value.to_string_canonical();
+
+        assert_eq!(canonical(Value::number(5.0)), "5");
+        assert_eq!(canonical(Value::number(-0.0)), "0");
+        assert_eq!(canonical(Value::number(0.25)), "0.25");
+        assert_eq!(canonical(Value::number(1e21)), "1000000000000000000000");
+        assert_eq!(canonical(Value::number(u64::MAX)), "18446744073709551615");
+    }
+
+    #[test]
+    fn canonical_equal_values_serialize_equally() {
+        let a = parse(String::from(r#"{"x": 1, "y": [1.0, {"q": 2e0, "p": null}]}"#)).unwrap();
+        let b = parse(String::from(r#"{ "y": [1, {"p": null, "q": 2}], "x": 1.0 }"#)).unwrap();
+
+        assert_eq!(a.to_string_canonical(), b.to_string_canonical());
+    }
+
+    #[test]
+    fn pretty_scalar() {
+        check_pretty(Value::number(1.5), Indent::Spaces(2), "1.5");
+    }
+
+    #[test]
+    fn pretty_empty_containers() {
+        check_pretty(Value::Array(vec![]), Indent::Spaces(2), "[]");
+        check_pretty(Value::object([]), Indent::Spaces(2), "{}");
+    }
+
+    #[test]
+    fn pretty_nested_with_spaces() {
+        let value = Value::object([(
+            "tags",
+            Value::Array(vec![Value::string("admin"), Value::Array(vec![])]),
+        )]);
+
+        check_pretty(
+            value,
+            Indent::Spaces(2),
+            "{\n  \"tags\": [\n    \"admin\",\n    []\n  ]\n}",
+        );
+    }
+
+    #[test]
+    fn pretty_nested_with_tabs() {
+        let value = Value::Array(vec![Value::object([("key", Value::Null)])]);
+
+        check_pretty(value, Indent::Tabs, "[\n\t{\n\t\t\"key\": null\n\t}\n]");
+    }
+
+    #[test]
+    fn pretty_accepts_plain_width() {
+        let value = Value::Array(vec![Value::Null]);
+
+        assert_eq!(value.to_string_pretty(4), "[\n    null\n]");
+    }
+}
@@ -0,0 +1,192 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::{Deref, Range};
+use std::sync::Arc;
+
+use crate::{parse_borrowed_with, Map, Number, ParseError, ParseOptions, Value, ValueRef};
+
+/// JSON value whose strings are slices of a shared input, see [`parse_shared`]
+///
+/// It owns a reference to the input, so it can outlive the parse and move to other threads,
+/// which parse the same input without copying it. Objects are kept like in [`ValueRef`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SharedValue {
+    Null,
+    Boolean(bool),
+    String(SharedStr),
+    Number(Number),
+    /// Number literal kept exactly as written, see [`ParseOptions::preserve_number_text`]
+    RawNumber(String),
+    Array(Vec<SharedValue>),
+    Object(Vec<(SharedStr, SharedValue)>),
+}
+
+/// String of a [`SharedValue`], a slice of the shared input unless it had escapes
+#[derive(Clone)]
+pub struct SharedStr {
+    repr: Repr,
+}
+
+#[derive(Clone)]
+enum Repr {
+    Slice(Arc<str>, Range<usize>),
+    Owned(Box<str>),
+}
+
+/// Parses a single value, keeping strings without escapes as slices of input
+pub fn parse_shared(input: Arc<str>) -> Result<SharedValue, ParseError> {
+    parse_shared_with(input, &ParseOptions::default())
+}
+
+pub fn parse_shared_with(input: Arc<str>, options: &ParseOptions) -> Result<SharedValue, ParseError> {
+    let value = parse_borrowed_with(&input, options)?;
+
+    Ok(share(value, &input))
+}
+
+/// Turns the strings borrowed from source into slices of it
+fn share(value: ValueRef<'_>, source: &Arc<str>) -> SharedValue {
+    match value {
+        ValueRef::Null => SharedValue::Null,
+        ValueRef::Boolean(boolean) => SharedValue::Boolean(boolean),
+        ValueRef::String(string) => SharedValue::String(SharedStr::new(string, source)),
+        ValueRef::Number(number) => SharedValue::Number(number),
+        ValueRef::RawNumber(text) => SharedValue::RawNumber(text),
+        ValueRef::Array(array) => SharedValue::Array(array.into_iter().map(|value| share(value, source)).collect()),
+        ValueRef::Object(entries) => SharedValue::Object(
+            entries.into_iter().map(|(key, value)| (SharedStr::new(key, source), share(value, source))).collect(),
+        ),
+    }
+}
+
+impl SharedValue {
+    /// Looks up a key of an object, `None` for missing keys and non-objects
+    pub fn get(&self, key: &str) -> Option<&SharedValue> {
+        match self {
+            SharedValue::Object(entries) => entries.iter().find(|(k, _)| &**k == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SharedValue::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Copies the strings out of the shared input into an owned [`Value`]
+    pub fn to_owned_value(&self) -> Value {
+        match self {
+            SharedValue::Null => Value::Null,
+            SharedValue::Boolean(boolean) => Value::Boolean(*boolean),
+            SharedValue::String(string) => Value::String(string.to_string()),
+            SharedValue::Number(number) => Value::Number(*number),
+            SharedValue::RawNumber(text) => Value::RawNumber(text.clone()),
+            SharedValue::Array(array) => Value::Array(array.iter().map(SharedValue::to_owned_value).collect()),
+            SharedValue::Object(entries) => Value::Object(
+                entries.iter().map(|(key, value)| (key.to_string(), value.to_owned_value())).collect::<Map>(),
+            ),
+        }
+    }
+}
+
+impl SharedStr {
+    fn new(string: Cow<'_, str>, source: &Arc<str>) -> Self {
+        let repr = match string {
+            Cow::Borrowed(slice) => {
+                let start = slice.as_ptr() as usize - source.as_ptr() as usize;
+                Repr::Slice(Arc::clone(source), start..start + slice.len())
+            }
+            Cow::Owned(string) => Repr::Owned(string.into_boxed_str()),
+        };
+
+        Self { repr }
+    }
+
+    /// Whether the string is a slice of the shared input rather than a copy
+    pub fn is_shared(&self) -> bool {
+        matches!(self.repr, Repr::Slice(..))
+    }
+}
+
+impl Deref for SharedStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match &self.repr {
+            Repr::Slice(source, range) => &source[range.clone()],
+            Repr::Owned(string) => string,
+        }
+    }
+}
+
+impl PartialEq for SharedStr {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl fmt::Debug for SharedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl fmt::Display for SharedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use crate::{parse, ParseError};
+    use super::{parse_shared, SharedValue};
+
+    #[test]
+    fn slices_the_shared_input() {
+        let input: Arc<str> = Arc::from(r#"{"name": "ada", "quote": "\"hi\"", "tags": ["a", 1]}"#);
+
+        let value = parse_shared(Arc::clone(&input)).unwrap();
+
+        let SharedValue::String(name) = value.get("name").unwrap() else { panic!("expected a string") };
+        let SharedValue::String(quote) = value.get("quote").unwrap() else { panic!("expected a string") };
+        assert_eq!(&**name, "ada");
+        assert!(name.is_shared());
+        assert_eq!(&**quote, "\"hi\"");
+        assert!(!quote.is_shared());
+        assert_eq!(value.to_owned_value(), parse(String::from(&*input)).unwrap());
+        assert_eq!(Arc::strong_count(&input), 6);
+    }
+
+    #[test]
+    fn outlives_the_parse_on_other_threads() {
+        let input: Arc<str> = Arc::from(r#"[{"id": "a"}, {"id": "b"}]"#);
+
+        let handles: Vec<_> = (0..2)
+            .map(|index| {
+                let input = Arc::clone(&input);
+                thread::spawn(move || {
+                    let SharedValue::Array(array) = parse_shared(input).unwrap() else { panic!("expected an array") };
+                    array.into_iter().nth(index).unwrap()
+                })
+            })
+            .collect();
+        let ids: Vec<String> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap().get("id").and_then(SharedValue::as_str).unwrap().to_owned())
+            .collect();
+
+        assert_eq!(ids, ["a", "b"]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse_shared(Arc::from("")), Err(ParseError::EmptyInput));
+        assert_eq!(parse_shared(Arc::from("[1] 2")), Err(ParseError::TrailingTokens));
+    }
+}
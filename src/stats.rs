@@ -0,0 +1,106 @@
+use crate::{parse_with_visitor, Number, ParseError, ParseOptions, Visitor};
+
+/// Counts of the values in a document, see [`document_stats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub objects: usize,
+    pub arrays: usize,
+    /// String values, object keys are not counted
+    pub strings: usize,
+    pub numbers: usize,
+    pub booleans: usize,
+    pub nulls: usize,
+    /// Deepest nesting of arrays and objects, a top-level container is at depth 1
+    pub max_depth: usize,
+}
+
+/// Counts the values of a single JSON document in one pass, without building a [`Value`](crate::Value)
+///
+/// Input is checked as [`parse`](crate::parse) checks it, so documents nested deeper than
+/// the default [`ParseOptions::max_depth`] fail instead.
+pub fn document_stats(input: &str) -> Result<Stats, ParseError> {
+    let mut counter = Counter::default();
+    parse_with_visitor(input, &ParseOptions::default(), &mut counter)?;
+
+    Ok(counter.stats)
+}
+
+#[derive(Default)]
+struct Counter {
+    stats: Stats,
+    depth: usize,
+}
+
+impl Counter {
+    fn begin(&mut self) {
+        self.depth += 1;
+        self.stats.max_depth = self.stats.max_depth.max(self.depth);
+    }
+}
+
+impl Visitor for Counter {
+    fn on_null(&mut self) {
+        self.stats.nulls += 1;
+    }
+
+    fn on_bool(&mut self, _value: bool) {
+        self.stats.booleans += 1;
+    }
+
+    fn on_number(&mut self, _number: Number) {
+        self.stats.numbers += 1;
+    }
+
+    fn on_string(&mut self, _value: &str) {
+        self.stats.strings += 1;
+    }
+
+    fn on_begin_array(&mut self) {
+        self.stats.arrays += 1;
+        self.begin();
+    }
+
+    fn on_end_array(&mut self) {
+        self.depth -= 1;
+    }
+
+    fn on_begin_object(&mut self) {
+        self.stats.objects += 1;
+        self.begin();
+    }
+
+    fn on_end_object(&mut self) {
+        self.depth -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::TokenParseError;
+    use crate::{Location, Token};
+    use super::{document_stats, Stats};
+
+    #[test]
+    fn counts_values() {
+        let actual = document_stats(r#"{"a": [1, 2.5, "x", true, false, null, [[]]], "b": {"c": {}}, "d": "y"}"#).unwrap();
+
+        assert_eq!(actual, Stats { objects: 3, arrays: 3, strings: 2, numbers: 2, booleans: 2, nulls: 1, max_depth: 4 });
+    }
+
+    #[test]
+    fn scalar_document() {
+        assert_eq!(document_stats("null"), Ok(Stats { nulls: 1, ..Stats::default() }));
+    }
+
+    #[test]
+    fn invalid_document() {
+        assert_eq!(
+            document_stats("[1 2]"),
+            Err(TokenParseError::ExpectedComma { found: Token::number(2), location: Location::new(1, 4, 3) }.into()),
+        );
+        assert_eq!(
+            document_stats(&"[".repeat(200)),
+            Err(TokenParseError::DepthLimitExceeded { location: Location::new(1, 129, 128) }.into()),
+        );
+    }
+}
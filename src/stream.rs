@@ -0,0 +1,406 @@
+use crate::borrowed::TokenRef;
+use crate::tokenize::{KeyPosition, RawToken, Token, Tokenizer};
+use crate::visit::{Structure, Visitor};
+use crate::{DuplicateKeyPolicy, Location, Map, Number, NumberParser, ParseError, ParseOptions, TokenParseError, Value};
+
+/// Parses a single value from text that arrives in chunks, like segments read from a socket
+///
+/// Every chunk is tokenized as it is fed and its tokens go straight into the value being built.
+/// A token cut in two by a chunk boundary is kept until the rest of it arrives, which is
+/// looked for without tokenizing it again. Errors show up as soon as the chunk that causes
+/// them is fed, except for errors inside a token cut in two, which show up once it is complete.
+#[derive(Debug)]
+pub struct StreamParser {
+    options: ParseOptions,
+    /// Text that isn't tokenized yet, it starts at `location` in the whole input
+    pending: String,
+    location: Location,
+    keys: KeyPosition,
+    /// Reading of `pending` while it can't hold a complete token yet, `None` when it may
+    scan: Option<Scan>,
+    structure: Structure,
+    builder: Builder,
+    /// Error the parser failed with, every later call fails with it again
+    error: Option<ParseError>,
+}
+
+impl StreamParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(mut self, options: &ParseOptions) -> Self {
+        self.options = options.clone();
+        self.builder = Builder::new(options);
+        self
+    }
+
+    /// Tokenizes the chunk, together with the end of the previous one that wasn't a complete token yet
+    ///
+    /// After an error the input can't be made valid by more chunks, feeding them fails again.
+    pub fn feed(&mut self, chunk: &str) -> Result<(), ParseError> {
+        if let Some(error) = &self.error {
+            return Err(repeat(error));
+        }
+
+        let start = self.pending.len();
+        self.pending.push_str(chunk);
+        if let Some(scan) = &mut self.scan
+            && !scan.completes(&self.pending[start..], &self.options)
+        {
+            return Ok(());
+        }
+
+        let result = self.tokenize(false);
+        self.fail_on(result)
+    }
+
+    /// Returns the value once all of the input was fed
+    pub fn finish(mut self) -> Result<Value, ParseError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        self.tokenize(true)?;
+        if !self.structure.has_started() {
+            return Err(ParseError::no_value(self.location));
+        }
+
+        self.structure.finish()?;
+        Ok(self.builder.value.expect("a complete structure holds a value"))
+    }
+
+    fn fail_on(&mut self, result: Result<(), ParseError>) -> Result<(), ParseError> {
+        if let Err(error) = &result {
+            self.error = Some(repeat(error));
+        }
+
+        result
+    }
+
+    /// Hands the complete tokens of the pending text to the value being built
+    ///
+    /// Before the end of input the last token is held back, as the next chunk may continue it,
+    /// and so is a token that fails right at the end of the pending text or a comment left open.
+    fn tokenize(&mut self, at_end: bool) -> Result<(), ParseError> {
+        let base = self.location.byte_offset;
+        let end = base + self.pending.len();
+        let mut tokenizer = Tokenizer::resume(&self.pending, self.location, &self.options, self.keys.clone());
+        let mut consumed = self.location;
+
+        loop {
+            match tokenizer.next_raw() {
+                Some(Ok((_, span))) if !at_end && span.end.byte_offset == end => break,
+                Some(Ok((raw, span))) => {
+                    if self.options.allow_unquoted_keys {
+                        self.keys.track(&raw);
+                    }
+                    let token = match raw {
+                        RawToken::Token(Token::String(string)) => TokenRef::String(string.into()),
+                        RawToken::Token(token) => TokenRef::Other(token),
+                        // without the quotes
+                        RawToken::Verbatim => TokenRef::String(
+                            self.pending[span.start.byte_offset - base + 1..span.end.byte_offset - base - 1].into(),
+                        ),
+                    };
+
+                    self.structure.push(token, span, &self.options, &mut self.builder)?;
+                    if let Some(message) = self.builder.number_error.take() {
+                        return Err(TokenParseError::NumberParserFailed { message, location: span.start }.into());
+                    }
+                    consumed = span.end;
+                }
+                Some(Err(_)) if !at_end && tokenizer.location().byte_offset == end => break,
+                Some(Err(err)) => return Err(err.into()),
+                None => {
+                    // only whitespace and comments are left, but a line comment may go on in the next chunk
+                    let mut scan = Scan::new();
+                    scan.completes(&self.pending[consumed.byte_offset - base..], &self.options);
+                    if at_end || !matches!(scan.state, ScanState::LineComment) {
+                        consumed = tokenizer.location();
+                    }
+                    break;
+                }
+            }
+        }
+
+        self.pending.drain(..consumed.byte_offset - base);
+        self.location = consumed;
+
+        let mut scan = Scan::new();
+        self.scan = (!at_end && !scan.completes(&self.pending, &self.options)).then_some(scan);
+        Ok(())
+    }
+}
+
+impl Default for StreamParser {
+    fn default() -> Self {
+        Self {
+            options: ParseOptions::default(),
+            pending: String::new(),
+            location: Location::start(),
+            keys: KeyPosition::default(),
+            scan: None,
+            structure: Structure::default(),
+            builder: Builder::new(&ParseOptions::default()),
+            error: None,
+        }
+    }
+}
+
+/// Copy of an error the parser failed with, which is always one found in the input
+fn repeat(error: &ParseError) -> ParseError {
+    match error {
+        ParseError::TokenizeError(err) => ParseError::TokenizeError(err.clone()),
+        ParseError::ParseError(err) => ParseError::ParseError(err.clone()),
+        ParseError::TrailingTokens => ParseError::TrailingTokens,
+        _ => unreachable!("feeding fails only on errors in the input"),
+    }
+}
+
+/// Rough reading of the text after the last complete token, telling when it completes another one
+///
+/// It only follows strings, comments and runs of number and literal characters, anything else
+/// counts as complete and is left to the tokenizer, so each character is read a fixed number of times.
+#[derive(Debug, Clone, Copy)]
+struct Scan {
+    state: ScanState,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ScanState {
+    /// Whitespace between tokens
+    Between,
+    /// First character of a comment
+    Slash,
+    LineComment,
+    /// Holds whether the previous character is `*`
+    BlockComment(bool),
+    /// Holds the opening quote and whether the previous character starts an escape
+    String(char, bool),
+    /// Number, literal or unquoted key
+    Bare,
+}
+
+impl Scan {
+    fn new() -> Self {
+        Self { state: ScanState::Between }
+    }
+
+    /// Reads on through `text`, returning whether a token may be complete by its end
+    fn completes(&mut self, text: &str, options: &ParseOptions) -> bool {
+        let bare = |ch: char| ch.is_alphanumeric() || matches!(ch, '-' | '+' | '.' | '_' | '$');
+
+        for ch in text.chars() {
+            self.state = match self.state {
+                ScanState::Between if ch.is_ascii_whitespace() => ScanState::Between,
+                ScanState::Between if ch == '/' && options.allow_comments => ScanState::Slash,
+                ScanState::Between if ch == '"' || (ch == '\'' && options.allow_single_quotes) => ScanState::String(ch, false),
+                ScanState::Between | ScanState::Bare if bare(ch) => ScanState::Bare,
+                ScanState::Slash if ch == '/' => ScanState::LineComment,
+                ScanState::Slash if ch == '*' => ScanState::BlockComment(false),
+                ScanState::LineComment if ch == '\n' => ScanState::Between,
+                ScanState::LineComment => ScanState::LineComment,
+                ScanState::BlockComment(true) if ch == '/' => ScanState::Between,
+                ScanState::BlockComment(_) => ScanState::BlockComment(ch == '*'),
+                ScanState::String(quote, false) if ch == quote => return true,
+                ScanState::String(quote, escaped) => ScanState::String(quote, !escaped && ch == '\\'),
+                ScanState::Between | ScanState::Slash | ScanState::Bare => return true,
+            };
+        }
+
+        false
+    }
+}
+
+/// Builds the value out of what [`Structure`] makes of the tokens, like `parse::parse_value` does
+#[derive(Debug)]
+struct Builder {
+    number_parser: Option<NumberParser>,
+    duplicate_keys: DuplicateKeyPolicy,
+    stack: Vec<Frame>,
+    /// The top-level value once it is complete
+    value: Option<Value>,
+    /// Message of `ParseOptions::number_parser` failing on the latest number
+    number_error: Option<String>,
+}
+
+/// Container that is still being built
+#[derive(Debug)]
+enum Frame {
+    Array(Vec<Value>),
+    /// Object together with the key of the value being built
+    Object(Map, String),
+}
+
+impl Builder {
+    fn new(options: &ParseOptions) -> Self {
+        Self {
+            number_parser: options.number_parser,
+            duplicate_keys: options.duplicate_keys,
+            stack: Vec::new(),
+            value: None,
+            number_error: None,
+        }
+    }
+
+    /// Hands the finished value to its container
+    fn add(&mut self, value: Value) {
+        match self.stack.last_mut() {
+            None => self.value = Some(value),
+            Some(Frame::Array(array)) => array.push(value),
+            Some(Frame::Object(map, key)) => {
+                let key = std::mem::take(key);
+                if self.duplicate_keys != DuplicateKeyPolicy::FirstWins || !map.contains_key(&key) {
+                    map.insert(key, value);
+                }
+            }
+        }
+    }
+}
+
+impl Visitor for Builder {
+    fn on_null(&mut self) {
+        self.add(Value::Null);
+    }
+
+    fn on_bool(&mut self, value: bool) {
+        self.add(Value::Boolean(value));
+    }
+
+    fn on_number(&mut self, number: Number) {
+        self.add(Value::Number(number));
+    }
+
+    fn on_raw_number(&mut self, text: &str) {
+        match self.number_parser.map(|number_parser| number_parser(text)) {
+            Some(Ok(value)) => self.add(value),
+            Some(Err(message)) => self.number_error = Some(message),
+            None => self.add(Value::RawNumber(text.to_owned())),
+        }
+    }
+
+    fn on_string(&mut self, value: &str) {
+        self.add(Value::String(value.to_owned()));
+    }
+
+    fn on_begin_array(&mut self) {
+        self.stack.push(Frame::Array(Vec::new()));
+    }
+
+    fn on_end_array(&mut self) {
+        if let Some(Frame::Array(array)) = self.stack.pop() {
+            self.add(Value::Array(array));
+        }
+    }
+
+    fn on_begin_object(&mut self) {
+        self.stack.push(Frame::Object(Map::new(), String::new()));
+    }
+
+    fn on_key(&mut self, key: &str) {
+        if let Some(Frame::Object(_, current)) = self.stack.last_mut() {
+            *current = key.to_owned();
+        }
+    }
+
+    fn on_end_object(&mut self) {
+        if let Some(Frame::Object(map, _)) = self.stack.pop() {
+            self.add(Value::Object(map));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::TokenParseError;
+    use crate::tokenize::Token;
+    use crate::{parse, Location, ParseError, ParseOptions, TokenizeError, Value};
+    use super::StreamParser;
+
+    fn parse_chunks(chunks: &[&str]) -> Result<Value, ParseError> {
+        let mut parser = StreamParser::new();
+        for chunk in chunks {
+            parser.feed(chunk)?;
+        }
+
+        parser.finish()
+    }
+
+    #[test]
+    fn parses_value_split_anywhere() {
+        let input = "\u{feff}{\"name\": \"a\\u00e9\\\"💩\", \"n\": [12.5e3, -7, true, null, false], \"o\": {}}";
+        let expected = parse(String::from(input)).unwrap();
+
+        for split in input.char_indices().map(|(i, _)| i) {
+            let (first, second) = input.split_at(split);
+            assert_eq!(parse_chunks(&[first, second]), Ok(expected.clone()), "split at {split}");
+        }
+
+        let chars: Vec<String> = input.chars().map(String::from).collect();
+        let chunks: Vec<&str> = chars.iter().map(String::as_str).collect();
+        assert_eq!(parse_chunks(&chunks), Ok(expected));
+    }
+
+    #[test]
+    fn number_continued_in_next_chunk() {
+        assert_eq!(parse_chunks(&["[12", "34]"]), Ok(Value::Array(vec![Value::number(1234)])));
+        assert_eq!(parse_chunks(&["12", "", "34"]), Ok(Value::number(1234)));
+    }
+
+    #[test]
+    fn keeps_locations_across_chunks() {
+        let actual = parse_chunks(&["[1,\n", "  2 ", "3]"]).unwrap_err();
+
+        assert_eq!(actual, TokenParseError::ExpectedComma { found: Token::number(3), location: Location::new(2, 5, 8) }.into());
+    }
+
+    #[test]
+    fn tokenizer_error_on_feed() {
+        let mut parser = StreamParser::new();
+
+        assert_eq!(parser.feed("[1, "), Ok(()));
+        assert_eq!(
+            parser.feed("?, 2]"),
+            Err(TokenizeError::CharNotRecognized { ch: '?', location: Location::new(1, 5, 4) }.into()),
+        );
+    }
+
+    #[test]
+    fn structure_error_on_feed() {
+        let mut parser = StreamParser::new();
+
+        assert_eq!(parser.feed("[1, 2"), Ok(()));
+        let error = TokenParseError::ExpectedComma { found: Token::number(3), location: Location::new(1, 7, 6) };
+        assert_eq!(parser.feed(" 3]"), Err(error.clone().into()));
+        assert_eq!(parser.feed("]"), Err(error.into()));
+    }
+
+    #[test]
+    fn truncated_input() {
+        assert_eq!(
+            parse_chunks(&["[\"ab", "c"]),
+            Err(TokenizeError::UnclosedQuotes { location: Location::new(1, 2, 1) }.into()),
+        );
+        assert_eq!(parse_chunks(&["[tr", "u"]), Err(TokenizeError::UnfinishedLiteralValue { location: Location::new(1, 5, 4) }.into()));
+        assert_eq!(parse_chunks(&["[1,", " 2"]), Err(TokenParseError::UnexpectedEof.into()));
+        assert_eq!(parse_chunks(&["1 ", "2"]), Err(ParseError::TrailingTokens));
+        assert_eq!(parse_chunks(&[]), Err(ParseError::EmptyInput));
+        assert_eq!(parse_chunks(&["", " ", "\n"]), Err(ParseError::BlankInput));
+    }
+
+    #[test]
+    fn applies_options() {
+        let options = ParseOptions { allow_unquoted_keys: true, allow_comments: true, ..ParseOptions::default() };
+        let mut parser = StreamParser::new().with_options(&options);
+
+        for chunk in ["{na", "me: [1, /* c", "omment */ 2], // li", "ne\n", "ok", ": true}"] {
+            parser.feed(chunk).unwrap();
+        }
+
+        assert_eq!(parser.finish(), Ok(Value::object([
+            ("name", Value::Array(vec![Value::number(1), Value::number(2)])),
+            ("ok", Value::Boolean(true)),
+        ])));
+    }
+}
@@ -1,6 +1,8 @@
-use std::{char, str::Chars, iter::Peekable, num::ParseFloatError};
+use std::{borrow::Cow, char, error::Error, fmt, iter::Peekable, num::ParseFloatError};
 
-#[derive(Debug, PartialEq)]
+use crate::{Location, Number, ParseOptions, Span};
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Token {
     /// `{`
     LeftBrace,
@@ -21,52 +23,480 @@ pub enum Token {
     /// `true`
     True,
     /// Any number literal
-    Number(f64),
+    Number(Number),
+    /// Number literal as written, with `ParseOptions::preserve_number_text` or `ParseOptions::number_parser`
+    RawNumber(String),
     /// Key of the key/value pair of string value
     String(String)
 }
 
+/// Token as the tokenizer finds it, before the contents of strings without escapes are copied out
+pub(crate) enum RawToken {
+    Token(Token),
+    /// String whose contents are exactly the source text between its quotes
+    Verbatim,
+}
+
+/// Token together with its place in the source text
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedToken {
+    pub token: Token,
+    pub span: Span,
+}
+
 #[cfg(test)]
 impl Token {
     pub(crate) fn string(input: &str) -> Self {
         Self::String(String::from(input))
     }
+
+    pub(crate) fn number(input: impl Into<Number>) -> Self {
+        Self::Number(input.into())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum TokenizeError {
-    UnfinishedLiteralValue,
-    InvalidNumber(String),
-    ParseNumberError(ParseFloatError),
-    UnclosedQuotes,
-    CharNotRecognized(char),
-    UnexpectedEof
+    UnfinishedLiteralValue { location: Location },
+    InvalidNumber { message: String, location: Location },
+    ParseNumberError { error: ParseFloatError, location: Location },
+    /// Number literal is too large to be represented
+    NumberOutOfRange { literal: String, location: Location },
+    UnclosedQuotes { location: Location },
+    /// String is longer than `ParseOptions::max_string_length`, holds where it starts
+    StringTooLong { location: Location },
+    CharNotRecognized { ch: char, location: Location },
+    InvalidControlCharacter { ch: char, location: Location },
+    InvalidEscape { ch: char, location: Location },
+    UnfinishedEscape { location: Location },
+    InvalidHexValue { location: Location },
+    InvalidCodePointValue { location: Location },
+    /// `\uD800` through `\uDFFF` escape that is not part of a surrogate pair
+    UnpairedSurrogate { location: Location },
+    UnclosedComment { location: Location },
+    UnexpectedEof { location: Location }
+}
+
+impl fmt::Display for TokenizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnfinishedLiteralValue { location } => write!(f, "unfinished literal at {location}"),
+            Self::InvalidNumber { message, location } => write!(f, "{message} at {location}"),
+            Self::ParseNumberError { error, location } => write!(f, "invalid number at {location}: {error}"),
+            Self::NumberOutOfRange { literal, location } => write!(f, "number {literal} at {location} is out of range"),
+            Self::UnclosedQuotes { location } => write!(f, "string starting at {location} is never closed"),
+            Self::StringTooLong { location } => write!(f, "string starting at {location} is too long"),
+            Self::CharNotRecognized { ch, location } => write!(f, "unexpected character {ch:?} at {location}"),
+            Self::InvalidControlCharacter { ch, location } => {
+                write!(f, "control character {ch:?} must be escaped at {location}")
+            }
+            Self::InvalidEscape { ch, location } => write!(f, "invalid escape `\\{ch}` at {location}"),
+            Self::UnfinishedEscape { location } => write!(f, "unfinished escape sequence at {location}"),
+            Self::InvalidHexValue { location } => write!(f, "invalid hex digit in escape at {location}"),
+            Self::InvalidCodePointValue { location } => write!(f, "escape at {location} is not a valid code point"),
+            Self::UnpairedSurrogate { location } => write!(f, "unpaired surrogate escape at {location}"),
+            Self::UnclosedComment { location } => write!(f, "comment starting at {location} is never closed"),
+            Self::UnexpectedEof { location } => write!(f, "unexpected end of input at {location}"),
+        }
+    }
+}
+
+impl TokenizeError {
+    /// Where in the source the error was found
+    pub fn location(&self) -> Location {
+        match self {
+            Self::UnfinishedLiteralValue { location }
+            | Self::InvalidNumber { location, .. }
+            | Self::ParseNumberError { location, .. }
+            | Self::NumberOutOfRange { location, .. }
+            | Self::UnclosedQuotes { location }
+            | Self::StringTooLong { location }
+            | Self::CharNotRecognized { location, .. }
+            | Self::InvalidControlCharacter { location, .. }
+            | Self::InvalidEscape { location, .. }
+            | Self::UnfinishedEscape { location }
+            | Self::InvalidHexValue { location }
+            | Self::InvalidCodePointValue { location }
+            | Self::UnpairedSurrogate { location }
+            | Self::UnclosedComment { location }
+            | Self::UnexpectedEof { location } => *location,
+        }
+    }
+}
+
+impl Error for TokenizeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ParseNumberError { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Source of characters that keeps track of the location in the source
+pub trait Cursor {
+    fn next(&mut self) -> Option<char>;
+
+    fn peek(&mut self) -> Option<char>;
+
+    /// Location of the next character
+    fn location(&self) -> Location;
+
+    /// Location of the character most recently returned by `next`
+    fn last_location(&self) -> Location;
+
+    /// Starts recording the consumed text, beginning with the character most recently returned by `next`
+    fn begin_capture(&mut self);
+
+    /// Text consumed since `begin_capture`
+    fn end_capture(&mut self) -> Cow<'_, str>;
+}
+
+/// Cursor over a string slice that steps through its bytes,
+/// decoding UTF-8 only for non-ASCII characters
+pub struct ByteCursor<'a> {
+    input: &'a str,
+    /// Byte offset of the start of `input` in the whole document
+    base: usize,
+    location: Location,
+    last_location: Location,
+    capture_start: usize,
+}
+
+impl<'a> ByteCursor<'a> {
+    fn new(input: &'a str) -> Self {
+        Self::resume(input, Location::start())
+    }
+
+    /// Cursor over the part of a document that starts at `start`
+    fn resume(input: &'a str, start: Location) -> Self {
+        let base = start.byte_offset;
+        let start = if start == Location::start() { start_after_bom(input.starts_with(BOM)) } else { start };
+        Self { input, base, location: start, last_location: start, capture_start: base }
+    }
+}
+
+impl Cursor for ByteCursor<'_> {
+    fn next(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.last_location = self.location;
+        self.location = self.location.advance(ch);
+
+        Some(ch)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        let offset = self.location.byte_offset - self.base;
+        let &byte = self.input.as_bytes().get(offset)?;
+
+        if byte.is_ascii() {
+            Some(char::from(byte))
+        } else {
+            self.input[offset..].chars().next()
+        }
+    }
+
+    fn location(&self) -> Location {
+        self.location
+    }
+
+    fn last_location(&self) -> Location {
+        self.last_location
+    }
+
+    fn begin_capture(&mut self) {
+        self.capture_start = self.last_location.byte_offset;
+    }
+
+    fn end_capture(&mut self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.input[self.capture_start - self.base..self.location.byte_offset - self.base])
+    }
+}
+
+/// Cursor over characters that are produced one by one, e.g. decoded from a reader
+pub struct CharCursor<I: Iterator<Item = char>> {
+    chars: Peekable<I>,
+    location: Location,
+    last_location: Location,
+    last_char: Option<char>,
+    capture: Option<String>,
+}
+
+impl<I: Iterator<Item = char>> CharCursor<I> {
+    fn new(chars: I) -> Self {
+        let mut chars = chars.peekable();
+        let start = start_after_bom(chars.next_if_eq(&BOM).is_some());
+
+        Self { chars, location: start, last_location: start, last_char: None, capture: None }
+    }
+}
+
+const BOM: char = '\u{feff}';
+
+/// RFC 8259 lets parsers ignore a byte order mark at the very start of the input,
+/// it counts towards byte offsets but not towards columns
+fn start_after_bom(has_bom: bool) -> Location {
+    let start = Location::start();
+    if has_bom {
+        Location { byte_offset: BOM.len_utf8(), ..start }
+    } else {
+        start
+    }
+}
+
+impl<I: Iterator<Item = char>> Cursor for CharCursor<I> {
+    fn next(&mut self) -> Option<char> {
+        let ch = self.chars.next()?;
+        self.last_location = self.location;
+        self.location = self.location.advance(ch);
+        self.last_char = Some(ch);
+        if let Some(capture) = &mut self.capture {
+            capture.push(ch);
+        }
+
+        Some(ch)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn location(&self) -> Location {
+        self.location
+    }
+
+    fn last_location(&self) -> Location {
+        self.last_location
+    }
+
+    fn begin_capture(&mut self) {
+        self.capture = Some(self.last_char.into_iter().collect());
+    }
+
+    fn end_capture(&mut self) -> Cow<'_, str> {
+        Cow::Owned(self.capture.take().unwrap_or_default())
+    }
+}
+
+/// Lazy iterator over the tokens of the input, stops after the first error
+pub struct Tokenizer<C: Cursor> {
+    chars: C,
+    options: ParseOptions,
+    failed: bool,
+    recover: bool,
+    /// Only tracked with `ParseOptions::allow_unquoted_keys`
+    keys: KeyPosition,
+}
+
+/// Where in the nesting of containers the tokenizer is, to know where an object key may appear
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KeyPosition {
+    /// Open containers, `true` for objects
+    containers: Vec<bool>,
+    /// Whether the next token is in the place of an object key
+    expects_key: bool,
+}
+
+impl KeyPosition {
+    /// Moves past the token
+    pub(crate) fn track(&mut self, token: &RawToken) {
+        match token {
+            RawToken::Token(Token::LeftBrace) => self.containers.push(true),
+            RawToken::Token(Token::LeftBracket) => self.containers.push(false),
+            RawToken::Token(Token::RightBrace | Token::RightBracket) => {
+                self.containers.pop();
+            }
+            _ => {}
+        }
+
+        let in_object = self.containers.last() == Some(&true);
+        self.expects_key = in_object && matches!(token, RawToken::Token(Token::LeftBrace | Token::Comma));
+    }
+}
+
+impl<'a> Tokenizer<ByteCursor<'a>> {
+    pub fn new(input: &'a str) -> Self {
+        Self::from_cursor(ByteCursor::new(input))
+    }
+
+    /// Continues tokenizing a document whose text before `input` ended at `start`,
+    /// with `keys` as they were there
+    pub(crate) fn resume(input: &'a str, start: Location, options: &ParseOptions, keys: KeyPosition) -> Self {
+        Self { keys, ..Self::from_cursor(ByteCursor::resume(input, start)).with_options(options) }
+    }
+}
+
+impl<I: Iterator<Item = char>> Tokenizer<CharCursor<I>> {
+    /// Tokenizes characters as they are produced, without holding the whole input
+    pub fn from_chars(chars: I) -> Self {
+        Self::from_cursor(CharCursor::new(chars))
+    }
+}
+
+impl<C: Cursor> Tokenizer<C> {
+    fn from_cursor(chars: C) -> Self {
+        Self {
+            chars,
+            options: ParseOptions::default(),
+            failed: false,
+            recover: false,
+            keys: KeyPosition::default(),
+        }
+    }
+
+    pub fn with_options(mut self, options: &ParseOptions) -> Self {
+        self.options = options.clone();
+        self
+    }
+
+    /// Keeps tokenizing after an error, resuming after the broken token
+    pub(crate) fn recovering(mut self) -> Self {
+        self.recover = true;
+        self
+    }
+
+    /// Location of the next character
+    pub(crate) fn location(&self) -> Location {
+        self.chars.location()
+    }
+}
+
+impl<C: Cursor> Tokenizer<C> {
+    /// Next token, strings without escapes are left in the source as [`RawToken::Verbatim`]
+    pub(crate) fn next_raw(&mut self) -> Option<Result<(RawToken, Span), TokenizeError>> {
+        if self.failed {
+            return None;
+        }
+
+        let mut first = None;
+        let token = match skip_whitespace(&mut self.chars, &self.options) {
+            Ok(()) => {
+                let c = self.chars.next()?;
+                first = Some(c);
+                make_token(&mut self.chars, c, &self.options, self.keys.expects_key)
+            }
+            Err(err) => Err(err),
+        };
+        if self.options.allow_unquoted_keys && let Ok((token, _)) = &token {
+            self.keys.track(token);
+        }
+        match &token {
+            Err(err) if self.recover => skip_broken_token(&mut self.chars, err, first),
+            _ => self.failed = token.is_err(),
+        }
+
+        Some(token)
+    }
 }
 
-pub fn tokenize(input: String) -> Result<Vec<Token>, TokenizeError> {
-    let mut chars = input.chars().peekable();
+impl<C: Cursor> Iterator for Tokenizer<C> {
+    type Item = Result<SpannedToken, TokenizeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (token, span) = match self.next_raw()? {
+            Ok(raw) => raw,
+            Err(err) => return Some(Err(err)),
+        };
+        let token = match token {
+            RawToken::Token(token) => token,
+            RawToken::Verbatim => {
+                // the capture of the string is still running and holds both quotes
+                let text = self.chars.end_capture();
+                Token::String(text[1..text.len() - 1].to_owned())
+            }
+        };
+
+        Some(Ok(SpannedToken { token, span }))
+    }
+}
 
-    let mut tokens = Vec::new();
+pub fn tokenize(input: String) -> Result<Vec<SpannedToken>, TokenizeError> {
+    Tokenizer::new(&input).collect()
+}
 
-    while let Some(c) = chars.next() {
-        let token = make_token(&mut chars,c)?;
-        tokens.push(token);
+/// Skips the rest of a token that failed to tokenize, `first` being the character it started with
+fn skip_broken_token<C: Cursor>(chars: &mut C, error: &TokenizeError, first: Option<char>) {
+    let in_string = matches!(
+        error,
+        TokenizeError::InvalidControlCharacter { .. }
+            | TokenizeError::InvalidEscape { .. }
+            | TokenizeError::UnfinishedEscape { .. }
+            | TokenizeError::InvalidHexValue { .. }
+            | TokenizeError::InvalidCodePointValue { .. }
+            | TokenizeError::UnpairedSurrogate { .. }
+            | TokenizeError::StringTooLong { .. }
+    );
+
+    if in_string && let Some(quote) = first {
+        // the rest of the string, up to and including its closing quote
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => {
+                    chars.next();
+                }
+                _ if ch == quote => break,
+                _ => {}
+            }
+        }
+    } else {
+        while let Some(ch) = chars.peek() {
+            if ch.is_ascii_whitespace() || matches!(ch, '[' | ']' | '{' | '}' | ',' | ':' | '"') {
+                break;
+            }
+            chars.next();
+        }
     }
-    
-    Ok(tokens)
 }
 
-fn make_token(chars: &mut Peekable<Chars<'_>>, mut ch: char) -> Result<Token, TokenizeError> {
-    while ch.is_ascii_whitespace() {
-        if chars.peek() == None {
-            return Err(TokenizeError::UnexpectedEof);
+/// Advances past whitespace and, when allowed, comments, so the next character starts a token
+fn skip_whitespace<C: Cursor>(chars: &mut C, options: &ParseOptions) -> Result<(), TokenizeError> {
+    while let Some(ch) = chars.peek() {
+        if ch == '/' && options.allow_comments {
+            chars.next();
+            skip_comment(chars)?;
+        } else if ch.is_ascii_whitespace() {
+            chars.next();
+        } else {
+            break;
         }
-        ch = chars.next().unwrap();
     }
 
+    Ok(())
+}
+
+fn make_token<C: Cursor>(
+    chars: &mut C,
+    ch: char,
+    options: &ParseOptions,
+    expects_key: bool,
+) -> Result<(RawToken, Span), TokenizeError> {
+    let start = chars.last_location();
+
+    let token = match ch {
+        '"' => tokenize_string(chars, '"', options)?,
+        '\'' if options.allow_single_quotes => tokenize_string(chars, '\'', options)?,
+        ch => RawToken::Token(tokenize_non_string(chars, ch, start, options, expects_key)?),
+    };
+
+    Ok((token, Span { start, end: chars.location() }))
+}
+
+fn tokenize_non_string<C: Cursor>(
+    chars: &mut C,
+    ch: char,
+    start: Location,
+    options: &ParseOptions,
+    expects_key: bool,
+) -> Result<Token, TokenizeError> {
     let token = match ch {
-        c if is_number(ch) => tokenize_float(chars, c)?,
-        '"' => tokenize_string(chars)?,
+        // checked first so keys like `null` or `true` stay keys
+        c if expects_key && is_identifier_start(c) => tokenize_identifier(chars),
+        c if is_number(ch) => tokenize_float(chars, c, options)?,
+        'N' if options.allow_nan_inf => tokenize_nan(chars)?,
+        'I' if options.allow_nan_inf => tokenize_infinity(chars, false)?,
+        // a number can't start with its decimal point, as in `.5`
+        '.' => return Err(invalid_number(start)),
+        '+' => return Err(TokenizeError::InvalidNumber { message: "leading plus not allowed".to_string(), location: start }),
         '[' => Token::LeftBracket,
         ']' => Token::RightBracket,
         '{' => Token::LeftBrace,
@@ -76,12 +506,53 @@ fn make_token(chars: &mut Peekable<Chars<'_>>, mut ch: char) -> Result<Token, To
         't' => tokenize_true(chars)?,
         'f' => tokenize_false(chars)?,
         'n' => tokenize_null(chars)?,
-        ch => return Err(TokenizeError::CharNotRecognized(ch)),
+        ch => return Err(TokenizeError::CharNotRecognized { ch, location: start }),
     };
 
     Ok(token)
 }
 
+/// Skips a `// line` or `/* block */` comment whose slash was just consumed
+fn skip_comment<C: Cursor>(chars: &mut C) -> Result<(), TokenizeError> {
+    let location = chars.last_location();
+
+    match chars.next() {
+        Some('/') => {
+            while let Some(c) = chars.next() {
+                if c == '\n' {
+                    break;
+                }
+            }
+            Ok(())
+        }
+        Some('*') => {
+            let mut previous = None;
+            while let Some(c) = chars.next() {
+                if previous == Some('*') && c == '/' {
+                    return Ok(());
+                }
+                previous = Some(c);
+            }
+            Err(TokenizeError::UnclosedComment { location })
+        }
+        _ => Err(TokenizeError::CharNotRecognized { ch: '/', location }),
+    }
+}
+
+fn is_identifier_start(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '_' || ch == '$'
+}
+
+/// Reads the rest of an unquoted key whose first character was just consumed
+fn tokenize_identifier<C: Cursor>(chars: &mut C) -> Token {
+    chars.begin_capture();
+    while chars.peek().is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$') {
+        chars.next();
+    }
+
+    Token::String(chars.end_capture().into_owned())
+}
+
 fn is_number(ch: char) -> bool {
     match ch {
         '-' => true,
@@ -90,60 +561,101 @@ fn is_number(ch: char) -> bool {
     }
 }
 
-fn tokenize_float(chars: &mut Peekable<Chars<'_>>, ch: char) -> Result<Token, TokenizeError> {
-    let mut unparsed_num = String::new();
-    unparsed_num.push(ch);
+fn tokenize_float<C: Cursor>(
+    chars: &mut C,
+    ch: char,
+    options: &ParseOptions,
+) -> Result<Token, TokenizeError> {
+    if ch == '-' && options.allow_nan_inf && chars.peek() == Some('I') {
+        chars.next();
+        return tokenize_infinity(chars, true);
+    }
 
-    if ch == '-' {
-        if chars.peek().is_some_and(|&c| c == '0') {
-            unparsed_num.push(chars.next().unwrap());
+    let location = chars.last_location();
+    chars.begin_capture();
 
-            if chars.peek().is_some_and(|&c| c.is_ascii_digit()) {
-                return  Err(TokenizeError::InvalidNumber("Invalid number provided.".to_string()));
-            }
-        }
+    // `-` alone, `-.5` and `-e5` have no integer part
+    if ch == '-' && !chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+        return Err(invalid_number(location));
     }
 
-    if ch == '0' {
-        if chars.peek().is_some_and(|&c| c.is_ascii_digit()) {
-            return Err(TokenizeError::InvalidNumber("Invalid number provided.".to_string()));
-        } 
+    // the integer part may only start with `0` when it is exactly `0`, with or without the sign
+    let starts_with_zero = if ch == '-' && chars.peek() == Some('0') {
+        chars.next();
+        true
+    } else {
+        ch == '0'
+    };
+
+    if starts_with_zero && chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+        return Err(invalid_number(location));
     }
 
     let mut has_decimal = false;
     let mut has_exponent = false;
 
-    while let Some(&c) = chars.peek() {
+    while let Some(c) = chars.peek() {
         match c {
-            c if c.is_ascii_digit() => unparsed_num.push(chars.next().unwrap()),
+            c if c.is_ascii_digit() => {
+                chars.next();
+            }
             c if is_exponenta(has_exponent, c, chars) => {
-                unparsed_num.push(chars.next().unwrap());
+                chars.next();
                 has_exponent = true;
                 
-                if chars.peek().is_some_and(|&c| c == '+' || c == '-' ) {
-                    unparsed_num.push(chars.next().unwrap());
+                if chars.peek().is_some_and(|c| c == '+' || c == '-' ) {
+                    chars.next();
                 }
 
                 if !chars.peek().is_some_and(|c| c.is_ascii_digit()) {
-                    return Err(TokenizeError::InvalidNumber("Invalid number provided.".to_string()));
+                    return Err(invalid_number(location));
                 }
             },
             c if is_decimal(has_decimal, has_exponent, c) => {
-                unparsed_num.push('.');
                 has_decimal = true;
                 chars.next();
+
+                // `f64::from_str` accepts `1.`, JSON requires digits after the point
+                if !chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    return Err(invalid_number(location));
+                }
             }
             _ => break,
         }
     }
 
+    // the whole literal matched the JSON number grammar by now, so the text kept as written is valid JSON
+    let unparsed_num = chars.end_capture();
+    if options.preserve_number_text || options.number_parser.is_some() {
+        return Ok(Token::RawNumber(unparsed_num.into_owned()));
+    }
+
+    if !has_decimal && !has_exponent && let Some(integer) = parse_integer(&unparsed_num) {
+        return Ok(Token::Number(integer));
+    }
+
+    // integers that overflow 128 bits are kept as floats as well
     match unparsed_num.parse::<f64>() {
-        Ok(f) => Ok(Token::Number(f)),
-        Err(e) => Err(TokenizeError::ParseNumberError(e))
+        // the literal itself is finite, so an infinity means it's too large for `f64`
+        Ok(f) if f.is_infinite() => Err(TokenizeError::NumberOutOfRange { literal: unparsed_num.into_owned(), location }),
+        Ok(f) => Ok(Token::Number(Number::from(f))),
+        Err(error) => Err(TokenizeError::ParseNumberError { error, location })
+    }
+}
+
+fn parse_integer(unparsed_num: &str) -> Option<Number> {
+    if unparsed_num.starts_with('-') {
+        unparsed_num.parse::<i128>().ok().map(Number::from)
+    } else {
+        unparsed_num.parse::<u128>().ok().map(Number::from)
     }
 }
 
-fn is_exponenta(has_exponent: bool, c: char, chars: &mut Peekable<Chars<'_>>) -> bool {
+fn invalid_number(location: Location) -> TokenizeError {
+    TokenizeError::InvalidNumber { message: "Invalid number provided.".to_string(), location }
+}
+
+fn is_exponenta<C: Cursor>(has_exponent: bool, c: char, chars: &mut C) -> bool {
     !has_exponent && matches!(c, 'e' | 'E') && chars.peek().is_some()
 }
 
@@ -151,30 +663,168 @@ fn is_decimal(has_decimal: bool, has_exponenta: bool, c: char) -> bool {
     c == '.' && !has_decimal && !has_exponenta
 }
 
-fn tokenize_string(chars: &mut Peekable<Chars<'_>>) -> Result<Token, TokenizeError> {
-    let mut string = String::new();
-    let mut is_closed: bool = false;
+/// Reads a string whose opening `quote` was just consumed, up to the matching closing quote
+fn tokenize_string<C: Cursor>(chars: &mut C, quote: char, options: &ParseOptions) -> Result<RawToken, TokenizeError> {
+    let location = chars.last_location();
+    chars.begin_capture();
+    // decoded contents, only built from the first escape on, before that the source text is the contents
+    let mut string: Option<String> = None;
+    // high surrogate escape waiting for its low half, with the location of its backslash
+    let mut high_surrogate: Option<(u32, Location)> = None;
+
+    while let Some(c) = chars.peek() {
+        let char_location = chars.location();
+
+        // in bytes of the contents, which are the source text between the quotes until decoding starts
+        let length = string.as_ref().map_or(char_location.byte_offset - location.byte_offset - 1, String::len);
+        if options.max_string_length.is_some_and(|max| length > max) {
+            return Err(TokenizeError::StringTooLong { location });
+        }
 
-    while let Some(c) = chars.next() {
-        if c == '"' {
-            is_closed = true;
-            break;
+        // checked before consuming the quote, so the string ends where it should even after the error
+        if c == quote && let Some((_, high_location)) = high_surrogate {
+            unpaired_surrogate(decoded(&mut string), high_location, options)?;
+            high_surrogate = None;
+        }
+        if c == '\\' && string.is_none() {
+            // the capture starts with the opening quote
+            string = Some(chars.end_capture()[1..].to_owned());
+        }
+        chars.next();
+
+        let unescaped = if c == '\\' { Some(tokenize_escape(chars, char_location, options)?) } else { None };
+
+        match (high_surrogate, unescaped) {
+            (Some((high, _)), Some(Unescaped::Surrogate(low))) if is_low_surrogate(low) => {
+                let code_point = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+                decoded(&mut string).push(char::from_u32(code_point).expect("surrogate pairs encode valid code points"));
+                high_surrogate = None;
+                continue;
+            }
+            (Some((_, high_location)), _) => {
+                unpaired_surrogate(decoded(&mut string), high_location, options)?;
+                high_surrogate = None;
+            }
+            (None, _) => {}
+        }
+
+        match unescaped {
+            Some(Unescaped::Char(ch)) => {
+                decoded(&mut string).push(ch);
+                continue;
+            }
+            Some(Unescaped::Surrogate(unit)) if is_low_surrogate(unit) => {
+                unpaired_surrogate(decoded(&mut string), char_location, options)?;
+                continue;
+            }
+            Some(Unescaped::Surrogate(unit)) => {
+                high_surrogate = Some((unit, char_location));
+                continue;
+            }
+            None => {}
         }
 
-        string.push(c);
+        if c == quote {
+            return Ok(string.map_or(RawToken::Verbatim, |string| RawToken::Token(Token::String(string))));
+        }
+
+        // RFC 8259 requires U+0000 through U+001F to be escaped
+        if ('\u{0}'..='\u{1f}').contains(&c) {
+            return Err(TokenizeError::InvalidControlCharacter { ch: c, location: char_location });
+        }
+
+        if let Some(string) = &mut string {
+            string.push(c);
+        }
     }
 
-    if !is_closed {
-        return Err(TokenizeError::UnclosedQuotes);
+    Err(TokenizeError::UnclosedQuotes { location })
+}
+
+/// Contents of a string that is being decoded, which they are as soon as an escape was found
+fn decoded(string: &mut Option<String>) -> &mut String {
+    string.as_mut().expect("strings are decoded from their first escape on")
+}
+
+/// Handles a surrogate escape that is not part of a pair
+fn unpaired_surrogate(string: &mut String, location: Location, options: &ParseOptions) -> Result<(), TokenizeError> {
+    if !options.replace_unpaired_surrogates {
+        return Err(TokenizeError::UnpairedSurrogate { location });
     }
 
-    Ok(Token::String(string))
+    string.push(char::REPLACEMENT_CHARACTER);
+    Ok(())
+}
+
+fn is_low_surrogate(unit: u32) -> bool {
+    (0xDC00..=0xDFFF).contains(&unit)
+}
+
+/// Result of decoding a single escape sequence
+#[derive(Clone, Copy)]
+enum Unescaped {
+    Char(char),
+    /// Half of a UTF-16 surrogate pair, which only forms a character together with the other half
+    Surrogate(u32),
+}
+
+/// Decodes the escape sequence whose backslash is at `location`
+fn tokenize_escape<C: Cursor>(chars: &mut C, location: Location, options: &ParseOptions) -> Result<Unescaped, TokenizeError> {
+    let ch = chars.next().ok_or(TokenizeError::UnfinishedEscape { location })?;
+
+    let unescaped = match ch {
+        '"' => '"',
+        '\'' if options.allow_single_quotes => '\'',
+        '\\' => '\\',
+        '/' => '/',
+        // `\b` (backspace) is a valid escape in JSON, but not Rust
+        'b' => '\u{8}',
+        // `\f` (formfeed) is a valid escape in JSON, but not Rust
+        'f' => '\u{c}',
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        'v' if options.lenient_escapes => '\u{b}',
+        '0' if options.lenient_escapes => '\0',
+        'x' if options.lenient_escapes => {
+            let mut sum = 0;
+            for _ in 0..2 {
+                sum = sum * 16 + hex_digit(chars, location)?;
+            }
+            char::from_u32(sum).expect("code points below U+0100 are characters")
+        }
+        'u' => {
+            let mut sum = 0;
+            for _ in 0..4 {
+                sum = sum * 16 + hex_digit(chars, location)?;
+            }
+            if (0xD800..=0xDFFF).contains(&sum) {
+                return Ok(Unescaped::Surrogate(sum));
+            }
+            char::from_u32(sum).ok_or(TokenizeError::InvalidCodePointValue { location })?
+        },
+        ch if options.lenient_escapes => ch,
+        ch => return Err(TokenizeError::InvalidEscape { ch, location }),
+    };
+
+    Ok(Unescaped::Char(unescaped))
+}
+
+/// Consumes the next hex digit of the escape at `location`
+///
+/// Any other character is left in place, so a closing quote still ends the string for error recovery.
+fn hex_digit<C: Cursor>(chars: &mut C, location: Location) -> Result<u32, TokenizeError> {
+    let next_char = chars.peek().ok_or(TokenizeError::UnfinishedEscape { location })?;
+    let digit = next_char.to_digit(16).ok_or(TokenizeError::InvalidHexValue { location })?;
+    chars.next();
+
+    Ok(digit)
 }
 
-fn tokenize_true(chars: &mut Peekable<Chars<'_>>) -> Result<Token, TokenizeError> {
+fn tokenize_true<C: Cursor>(chars: &mut C) -> Result<Token, TokenizeError> {
     for expected_char in "rue".chars() {
-        if chars.peek() != Some(&expected_char) {
-            return Err(TokenizeError::UnfinishedLiteralValue)
+        if chars.peek() != Some(expected_char) {
+            return Err(TokenizeError::UnfinishedLiteralValue { location: chars.location() })
         }
         chars.next();
     }
@@ -182,10 +832,10 @@ fn tokenize_true(chars: &mut Peekable<Chars<'_>>) -> Result<Token, TokenizeError
     Ok(Token::True)
 }
 
-fn tokenize_false(chars: &mut Peekable<Chars<'_>>) -> Result<Token, TokenizeError> {
+fn tokenize_false<C: Cursor>(chars: &mut C) -> Result<Token, TokenizeError> {
     for expected_char in "alse".chars() {
-        if chars.peek() != Some(&expected_char) {
-            return Err(TokenizeError::UnfinishedLiteralValue)
+        if chars.peek() != Some(expected_char) {
+            return Err(TokenizeError::UnfinishedLiteralValue { location: chars.location() })
         }
         chars.next();
     }
@@ -193,10 +843,10 @@ fn tokenize_false(chars: &mut Peekable<Chars<'_>>) -> Result<Token, TokenizeErro
     Ok(Token::False)
 }
 
-fn tokenize_null(chars: &mut Peekable<Chars<'_>>) -> Result<Token, TokenizeError> {
+fn tokenize_null<C: Cursor>(chars: &mut C) -> Result<Token, TokenizeError> {
     for expected_char in "ull".chars() {
-        if chars.peek() != Some(&expected_char) {
-            return Err(TokenizeError::UnfinishedLiteralValue);
+        if chars.peek() != Some(expected_char) {
+            return Err(TokenizeError::UnfinishedLiteralValue { location: chars.location() });
         }
         chars.next();
     }
@@ -204,19 +854,51 @@ fn tokenize_null(chars: &mut Peekable<Chars<'_>>) -> Result<Token, TokenizeError
     Ok(Token::Null)
 }
 
+fn tokenize_nan<C: Cursor>(chars: &mut C) -> Result<Token, TokenizeError> {
+    for expected_char in "aN".chars() {
+        if chars.peek() != Some(expected_char) {
+            return Err(TokenizeError::UnfinishedLiteralValue { location: chars.location() });
+        }
+        chars.next();
+    }
+
+    Ok(Token::Number(Number::from(f64::NAN)))
+}
+
+fn tokenize_infinity<C: Cursor>(chars: &mut C, negative: bool) -> Result<Token, TokenizeError> {
+    for expected_char in "nfinity".chars() {
+        if chars.peek() != Some(expected_char) {
+            return Err(TokenizeError::UnfinishedLiteralValue { location: chars.location() });
+        }
+        chars.next();
+    }
+
+    let infinity = if negative { f64::NEG_INFINITY } else { f64::INFINITY };
+    Ok(Token::Number(Number::from(infinity)))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tokenize::TokenizeError;
+    use crate::{Location, ParseOptions, Span};
 
-    use super::{tokenize, Token};
+    use super::{tokenize, SpannedToken, Token, Tokenizer};
+
+    fn tokens(input: String) -> Vec<Token> {
+        tokenize(input)
+            .unwrap()
+            .into_iter()
+            .map(|spanned| spanned.token)
+            .collect()
+    }
 
     // int
     #[test]
     fn integer() {
         let input = String::from("123");
-        let expected = [Token::Number(123.0)];
+        let expected = [Token::number(123.0)];
 
-        let actual = tokenize(input).unwrap();
+        let actual = tokens(input);
 
         assert_eq!(actual, expected)
     }
@@ -224,9 +906,9 @@ mod tests {
     #[test]
     fn negative_integer() {
         let input = String::from("-123");
-        let expected = [Token::Number(-123.0)];
+        let expected = [Token::number(-123.0)];
 
-        let actual = tokenize(input).unwrap();
+        let actual = tokens(input);
 
         assert_eq!(actual, expected)
     }
@@ -234,8 +916,10 @@ mod tests {
         #[test]
     fn double_negative_integer() {
         let input = String::from("--123");
-        let expected_error = input.parse::<f64>().unwrap_err();
-        let expected = TokenizeError::ParseNumberError(expected_error);
+        let expected = TokenizeError::InvalidNumber {
+            message: "Invalid number provided.".to_string(),
+            location: Location::new(1, 1, 0),
+        };
 
         let actual = tokenize(input).unwrap_err();
 
@@ -245,7 +929,10 @@ mod tests {
     #[test]
     fn double_zero() {
         let input = String::from("00");
-        let expected = TokenizeError::InvalidNumber("Invalid number provided.".to_string());
+        let expected = TokenizeError::InvalidNumber {
+            message: "Invalid number provided.".to_string(),
+            location: Location::new(1, 1, 0),
+        };
 
         let actual = tokenize(input).unwrap_err();
 
@@ -255,72 +942,447 @@ mod tests {
     #[test]
     fn neagtive_double_zero() {
         let input = String::from("-00");
-        let expected = TokenizeError::InvalidNumber("Invalid number provided.".to_string());
+        let expected = TokenizeError::InvalidNumber {
+            message: "Invalid number provided.".to_string(),
+            location: Location::new(1, 1, 0),
+        };
 
         let actual = tokenize(input).unwrap_err();
 
         assert_eq!(actual, expected)
     }
 
-    // string
     #[test]
-    fn string() {
-        let input = String::from("\"string\"");
-        let expected = [Token::string("string")];
+    fn leading_zeros() {
+        for input in ["01", "-01", "00", "-00", "0123", "-0123", "[1, 007]"] {
+            let actual = tokenize(String::from(input)).unwrap_err();
 
-        let actual = tokenize(input).unwrap();
-
-        assert_eq!(actual, expected)
+            assert!(matches!(actual, TokenizeError::InvalidNumber { .. }), "{input} was not rejected");
+        }
     }
 
     #[test]
-    fn unclosed_quotes() {
-        let input = String::from("\"string");
-        let expected = TokenizeError::UnclosedQuotes;
+    fn single_zero() {
+        let input = String::from("[0, -0, 0.5, -0.5, 0e1, 10]");
+        let expected = [
+            Token::LeftBracket,
+            Token::number(0),
+            Token::Comma,
+            Token::number(0),
+            Token::Comma,
+            Token::number(0.5),
+            Token::Comma,
+            Token::number(-0.5),
+            Token::Comma,
+            Token::number(0.0),
+            Token::Comma,
+            Token::number(10),
+            Token::RightBracket,
+        ];
 
-        let actual = tokenize(input).unwrap_err();
+        let actual = tokens(input);
 
         assert_eq!(actual, expected)
     }
 
-    // decimal
     #[test]
-    fn decimal() {
-        let input = String::from("0.88");
-        let expected = [Token::Number(0.88)];
+    fn missing_fraction_digits() {
+        for input in ["1.", "-3.", "[1., 2]", "1.e5"] {
+            let actual = tokenize(String::from(input)).unwrap_err();
 
-        let actual = tokenize(input).unwrap();
-
-        assert_eq!(actual, expected)
+            assert!(matches!(actual, TokenizeError::InvalidNumber { .. }), "{input} was not rejected");
+        }
     }
 
     #[test]
-    fn negative_decimal() {
-        let input = String::from("-0.88");
-        let expected = [Token::Number(-0.88)];
+    fn leading_decimal_point() {
+        let input = String::from("[.5]");
+        let expected = TokenizeError::InvalidNumber {
+            message: "Invalid number provided.".to_string(),
+            location: Location::new(1, 2, 1),
+        };
 
-        let actual = tokenize(input).unwrap();
+        let actual = tokenize(input).unwrap_err();
 
         assert_eq!(actual, expected)
     }
 
-    // exponent
     #[test]
-    fn exponent() {
-        let input = String::from("0.5e2");
-        let expected = [Token::Number(0.5e2)];
+    fn sign_without_digits() {
+        for (input, column) in [("-.5", 1), ("-", 1), ("-e5", 1), ("[-]", 2)] {
+            let expected = TokenizeError::InvalidNumber {
+                message: "Invalid number provided.".to_string(),
+                location: Location::new(1, column, column - 1),
+            };
 
-        let actual = tokenize(input).unwrap();
+            let actual = tokenize(String::from(input)).unwrap_err();
 
-        assert_eq!(actual, expected)
+            assert_eq!(actual, expected, "{input}");
+        }
     }
 
     #[test]
-    fn negative_exponent() {
+    fn leading_plus() {
+        let input = String::from("[+5]");
+        let expected = TokenizeError::InvalidNumber {
+            message: "leading plus not allowed".to_string(),
+            location: Location::new(1, 2, 1),
+        };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn number_out_of_range() {
+        let input = String::from("[1e400]");
+        let expected = TokenizeError::NumberOutOfRange {
+            literal: "1e400".to_string(),
+            location: Location::new(1, 2, 1),
+        };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn negative_number_out_of_range() {
+        let input = String::from("-1.5e309");
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert!(matches!(actual, TokenizeError::NumberOutOfRange { .. }))
+    }
+
+    #[test]
+    fn tiny_number_underflows_to_zero() {
+        let input = String::from("1e-400");
+        let expected = [Token::number(0.0)];
+
+        let actual = tokens(input);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn raw_number_text() {
+        let options = ParseOptions { preserve_number_text: true, ..ParseOptions::default() };
+
+        let actual: Vec<Token> = Tokenizer::new("[1.10, -0, 1e400, 123456789012345678901234567890]")
+            .with_options(&options)
+            .map(|spanned| spanned.unwrap().token)
+            .filter(|token| matches!(token, Token::RawNumber(_)))
+            .collect();
+
+        assert_eq!(actual, [
+            Token::RawNumber(String::from("1.10")),
+            Token::RawNumber(String::from("-0")),
+            Token::RawNumber(String::from("1e400")),
+            Token::RawNumber(String::from("123456789012345678901234567890")),
+        ]);
+    }
+
+    #[test]
+    fn raw_number_text_is_validated() {
+        let options = ParseOptions { preserve_number_text: true, ..ParseOptions::default() };
+
+        for input in ["01", "-", "[-]", "-.5", "-e5", "--1", "1.", "1e", "1e+", "[.5]", "[+1]"] {
+            let strict = tokenize(String::from(input)).unwrap_err();
+            let preserved = Tokenizer::new(input).with_options(&options).collect::<Result<Vec<_>, _>>().unwrap_err();
+
+            assert!(matches!(strict, TokenizeError::InvalidNumber { .. }), "{input} was not rejected");
+            assert_eq!(preserved, strict, "{input}");
+        }
+    }
+
+    #[test]
+    fn large_integer_keeps_precision() {
+        let input = String::from("9007199254740993");
+        let expected = [Token::number(9007199254740993u64)];
+
+        let actual = tokens(input);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn wide_integers_keep_precision() {
+        let input = String::from("[18446744073709551617, -170141183460469231731687303715884105728]");
+
+        let actual = tokens(input);
+
+        assert_eq!(actual[1], Token::number(18446744073709551617u128));
+        assert_eq!(actual[3], Token::number(i128::MIN));
+    }
+
+    #[test]
+    fn integer_overflow_becomes_float() {
+        let input = String::from("340282366920938463463374607431768211456");
+
+        let actual = tokens(input);
+
+        assert!(matches!(&actual[..], [Token::Number(n)] if n.is_f64()))
+    }
+
+    #[test]
+    fn integer_and_float_kinds() {
+        let input = String::from("[1, 1.0, 1e0]");
+
+        let actual = tokens(input);
+
+        assert!(matches!(&actual[1], Token::Number(n) if n.is_u64()));
+        assert!(matches!(&actual[3], Token::Number(n) if n.is_f64()));
+        assert!(matches!(&actual[5], Token::Number(n) if n.is_f64()));
+    }
+
+    // string
+    #[test]
+    fn string() {
+        let input = String::from("\"string\"");
+        let expected = [Token::string("string")];
+
+        let actual = tokens(input);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn unclosed_quotes() {
+        let input = String::from("\"string");
+        let expected = TokenizeError::UnclosedQuotes { location: Location::new(1, 1, 0) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn escaped_closing_quote_leaves_string_unclosed() {
+        let input = String::from(r#""abc\""#);
+        let expected = TokenizeError::UnclosedQuotes { location: Location::new(1, 1, 0) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn escaped_backslash_before_closing_quote() {
+        let input = String::from(r#"["abc\\", 1]"#);
+        let expected = [Token::LeftBracket, Token::string("abc\\"), Token::Comma, Token::number(1), Token::RightBracket];
+
+        let actual = tokens(input);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn string_with_escapes() {
+        let input = String::from(r#""\"quoted\" \\ \/ \n \t \u00e9""#);
+        let expected = [Token::string("\"quoted\" \\ / \n \t é")];
+
+        let actual = tokens(input);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn string_with_emoji() {
+        let input = String::from("\"💩💩💩\"");
+        let expected = [Token::string("💩💩💩")];
+
+        let actual = tokens(input);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn unknown_escape() {
+        let input = String::from(r#""a\xb""#);
+        let expected = TokenizeError::InvalidEscape { ch: 'x', location: Location::new(1, 3, 2) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn unknown_escapes_are_errors() {
+        for (input, ch) in [(r#""\q""#, 'q'), (r#""\x""#, 'x'), (r#""\ ""#, ' ')] {
+            let actual = tokenize(String::from(input)).unwrap_err();
+
+            assert_eq!(actual, TokenizeError::InvalidEscape { ch, location: Location::new(1, 2, 1) }, "{input}");
+        }
+    }
+
+    #[test]
+    fn lone_backslash_at_end_of_input() {
+        let input = String::from(r#""abc\"#);
+        let expected = TokenizeError::UnfinishedEscape { location: Location::new(1, 5, 4) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn lenient_unknown_escape_passes_through() {
+        let options = ParseOptions { lenient_escapes: true, ..ParseOptions::default() };
+
+        let actual = Tokenizer::new(r#""\q\%""#).with_options(&options).next().unwrap().unwrap();
+
+        assert_eq!(actual.token, Token::string("q%"));
+    }
+
+    #[test]
+    fn invalid_hex_escape() {
+        let input = String::from(r#""ab\uzzzz""#);
+        let expected = TokenizeError::InvalidHexValue { location: Location::new(1, 4, 3) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn unfinished_hex_escape() {
+        let input = String::from(r#""\u12"#);
+        let expected = TokenizeError::UnfinishedEscape { location: Location::new(1, 2, 1) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn lone_surrogate_escape() {
+        let input = String::from(r#""\ud800""#);
+        let expected = TokenizeError::UnpairedSurrogate { location: Location::new(1, 2, 1) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn surrogate_pair_escape() {
+        let input = String::from(r#""\ud83d\ude00 \uD834\uDD1E""#);
+        let expected = [Token::string("\u{1F600} \u{1D11E}")];
+
+        let actual = tokens(input);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn unpaired_surrogates_are_rejected() {
+        let cases = [
+            (r#""\ude00""#, 1),
+            (r#""a\ud83d""#, 2),
+            (r#""\ud83d\n""#, 1),
+            (r#""\ud83dx""#, 1),
+            (r#""\ud83d\ud83d\ude00""#, 1),
+            (r#""\ud83d\u0041""#, 1),
+        ];
+
+        for (input, offset) in cases {
+            let expected = TokenizeError::UnpairedSurrogate { location: Location::new(1, offset + 1, offset) };
+
+            let actual = tokenize(String::from(input)).unwrap_err();
+
+            assert_eq!(actual, expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn unpaired_surrogates_are_replaced() {
+        let options = ParseOptions { replace_unpaired_surrogates: true, ..ParseOptions::default() };
+        let cases = [
+            (r#""\ude00""#, "\u{FFFD}"),
+            (r#""a\ud83d""#, "a\u{FFFD}"),
+            (r#""\ud83d\n""#, "\u{FFFD}\n"),
+            (r#""\ud83dx""#, "\u{FFFD}x"),
+            (r#""\ud83d\ud83d\ude00""#, "\u{FFFD}\u{1F600}"),
+            (r#""\ud83d\u0041""#, "\u{FFFD}A"),
+        ];
+
+        for (input, expected) in cases {
+            let actual = Tokenizer::new(input).with_options(&options).next().unwrap().unwrap().token;
+
+            assert_eq!(actual, Token::string(expected), "{input}");
+        }
+    }
+
+    #[test]
+    fn raw_newline_in_string() {
+        let input = String::from("\"a\nb\"");
+        let expected = TokenizeError::InvalidControlCharacter { ch: '\n', location: Location::new(1, 3, 2) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn raw_tab_in_string() {
+        let input = String::from("[\"\t\"]");
+        let expected = TokenizeError::InvalidControlCharacter { ch: '\t', location: Location::new(1, 3, 2) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn delete_char_in_string() {
+        let input = String::from("\"\u{7f}\"");
+        let expected = [Token::string("\u{7f}")];
+
+        let actual = tokens(input);
+
+        assert_eq!(actual, expected)
+    }
+
+    // decimal
+    #[test]
+    fn decimal() {
+        let input = String::from("0.88");
+        let expected = [Token::number(0.88)];
+
+        let actual = tokens(input);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn negative_decimal() {
+        let input = String::from("-0.88");
+        let expected = [Token::number(-0.88)];
+
+        let actual = tokens(input);
+
+        assert_eq!(actual, expected)
+    }
+
+    // exponent
+    #[test]
+    fn exponent() {
+        let input = String::from("0.5e2");
+        let expected = [Token::number(0.5e2)];
+
+        let actual = tokens(input);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn negative_exponent() {
         let input = String::from("-0.5e2");
-        let expected = [Token::Number(-0.5e2)];
+        let expected = [Token::number(-0.5e2)];
 
-        let actual = tokenize(input).unwrap();
+        let actual = tokens(input);
 
         assert_eq!(actual, expected)
     }
@@ -331,7 +1393,7 @@ mod tests {
         let input = String::from(",");
         let expected = [Token::Comma];
 
-        let actual = tokenize(input).unwrap();
+        let actual = tokens(input);
 
         assert_eq!(actual, expected);
     }
@@ -348,7 +1410,7 @@ mod tests {
             Token::Colon,
         ];
 
-        let actual = tokenize(input).unwrap();
+        let actual = tokens(input);
 
         assert_eq!(actual, expected);
     }
@@ -359,7 +1421,7 @@ mod tests {
         let input = String::from("null");
         let expected = [Token::Null];
 
-        let actual = tokenize(input).unwrap();
+        let actual = tokens(input);
 
         assert_eq!(actual, expected);
     }
@@ -369,7 +1431,7 @@ mod tests {
         let input = String::from("true");
         let expected = [Token::True];
 
-        let actual = tokenize(input).unwrap();
+        let actual = tokens(input);
 
         assert_eq!(actual, expected);
     }
@@ -379,7 +1441,7 @@ mod tests {
         let input = String::from("false");
         let expected = [Token::False];
 
-        let actual = tokenize(input).unwrap();
+        let actual = tokens(input);
 
         assert_eq!(actual, expected);
     }
@@ -389,8 +1451,355 @@ mod tests {
         let input = String::from("true,");
         let expected = [Token::True, Token::Comma];
 
-        let actual = tokenize(input).unwrap();
+        let actual = tokens(input);
 
         assert_eq!(actual, expected);
     }
-}
\ No newline at end of file
+
+    // iterator
+    #[test]
+    fn tokenizer_is_lazy() {
+        let mut tokenizer = Tokenizer::new("[true ?");
+
+        assert_eq!(tokenizer.next().map(|t| t.unwrap().token), Some(Token::LeftBracket));
+        assert_eq!(tokenizer.next().map(|t| t.unwrap().token), Some(Token::True));
+        assert!(matches!(tokenizer.next(), Some(Err(TokenizeError::CharNotRecognized { ch: '?', .. }))));
+        assert!(tokenizer.next().is_none());
+    }
+
+    #[test]
+    fn byte_and_char_cursors_agree() {
+        let inputs = [
+            "{\"ключ\": [1, -0.5e3, 18446744073709551616, \"é\\n😀\"],\n\"b\": null}",
+            "[1, 2 é]",
+            "[1e400]",
+            "-",
+        ];
+
+        for input in inputs {
+            let bytes: Vec<_> = Tokenizer::new(input).collect();
+            let chars: Vec<_> = Tokenizer::from_chars(input.chars()).collect();
+
+            assert_eq!(bytes, chars, "{input}");
+        }
+    }
+
+    // comments
+    fn tokens_with_comments(input: &str) -> Result<Vec<Token>, TokenizeError> {
+        let options = ParseOptions { allow_comments: true, ..ParseOptions::default() };
+
+        Tokenizer::new(input)
+            .with_options(&options)
+            .map(|spanned| spanned.map(|spanned| spanned.token))
+            .collect()
+    }
+
+    #[test]
+    fn line_comment() {
+        let actual = tokens_with_comments("[1, // first\n 2]").unwrap();
+
+        assert_eq!(actual, [Token::LeftBracket, Token::number(1), Token::Comma, Token::number(2), Token::RightBracket]);
+    }
+
+    #[test]
+    fn block_comment() {
+        let actual = tokens_with_comments("/* leading **/ true /* a\n b */ ,").unwrap();
+
+        assert_eq!(actual, [Token::True, Token::Comma]);
+    }
+
+    #[test]
+    fn unclosed_block_comment() {
+        let actual = tokens_with_comments("true /* never closed *").unwrap_err();
+
+        assert_eq!(actual, TokenizeError::UnclosedComment { location: Location::new(1, 6, 5) });
+    }
+
+    #[test]
+    fn single_slash() {
+        let actual = tokens_with_comments("/ true").unwrap_err();
+
+        assert_eq!(actual, TokenizeError::CharNotRecognized { ch: '/', location: Location::new(1, 1, 0) });
+    }
+
+    #[test]
+    fn comments_rejected_by_default() {
+        let input = String::from("// comment\ntrue");
+        let expected = TokenizeError::CharNotRecognized { ch: '/', location: Location::new(1, 1, 0) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected);
+    }
+
+    // display
+    #[test]
+    fn error_message() {
+        let input = String::from("[\n  ?]");
+
+        let actual = tokenize(input).unwrap_err().to_string();
+
+        assert_eq!(actual, "unexpected character '?' at line 2, column 3");
+    }
+
+    // NaN and infinities
+    #[test]
+    fn nan_and_infinities() {
+        let options = ParseOptions { allow_nan_inf: true, ..ParseOptions::default() };
+
+        let actual: Vec<Token> = Tokenizer::new("[NaN, Infinity, -Infinity]")
+            .with_options(&options)
+            .map(|spanned| spanned.unwrap().token)
+            .collect();
+
+        assert!(matches!(&actual[1], Token::Number(n) if n.as_f64().is_nan()));
+        assert_eq!(actual[3], Token::number(f64::INFINITY));
+        assert_eq!(actual[5], Token::number(f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn unfinished_infinity() {
+        let options = ParseOptions { allow_nan_inf: true, ..ParseOptions::default() };
+
+        let actual = Tokenizer::new("-Inf").with_options(&options).next().unwrap().unwrap_err();
+
+        assert_eq!(actual, TokenizeError::UnfinishedLiteralValue { location: Location::new(1, 5, 4) });
+    }
+
+    #[test]
+    fn nan_and_infinities_rejected_by_default() {
+        let actual = tokenize(String::from("NaN")).unwrap_err();
+        assert_eq!(actual, TokenizeError::CharNotRecognized { ch: 'N', location: Location::new(1, 1, 0) });
+
+        let actual = tokenize(String::from("-Infinity")).unwrap_err();
+        assert!(matches!(actual, TokenizeError::InvalidNumber { .. }));
+    }
+
+    // unquoted keys
+    #[test]
+    fn unquoted_keys() {
+        let options = ParseOptions { allow_unquoted_keys: true, ..ParseOptions::default() };
+
+        let actual: Vec<Token> = Tokenizer::new("{name: [true], $id_2: null, null: {}}")
+            .with_options(&options)
+            .map(|spanned| spanned.unwrap().token)
+            .collect();
+
+        assert_eq!(actual[1], Token::string("name"));
+        assert_eq!(actual[4], Token::True);
+        assert_eq!(actual[7], Token::string("$id_2"));
+        assert_eq!(actual[9], Token::Null);
+        assert_eq!(actual[11], Token::string("null"));
+    }
+
+    #[test]
+    fn unquoted_values_rejected() {
+        let options = ParseOptions { allow_unquoted_keys: true, ..ParseOptions::default() };
+
+        let actual = Tokenizer::new("{a: [1, b]}").with_options(&options).find_map(Result::err).unwrap();
+
+        assert_eq!(actual, TokenizeError::CharNotRecognized { ch: 'b', location: Location::new(1, 9, 8) });
+    }
+
+    #[test]
+    fn unquoted_keys_rejected_by_default() {
+        let actual = tokenize(String::from("{name: 1}")).unwrap_err();
+
+        assert_eq!(actual, TokenizeError::UnfinishedLiteralValue { location: Location::new(1, 3, 2) });
+    }
+
+    // string length limit
+    #[test]
+    fn string_within_length_limit() {
+        let options = ParseOptions { max_string_length: Some(4), ..ParseOptions::default() };
+
+        let actual: Vec<Token> = Tokenizer::new(r#"["abcd", "é\n", "\u00e9\u00e9"]"#)
+            .with_options(&options)
+            .map(|spanned| spanned.unwrap().token)
+            .collect();
+
+        assert_eq!(actual[1], Token::string("abcd"));
+        assert_eq!(actual[3], Token::string("é\n"));
+        assert_eq!(actual[5], Token::string("éé"));
+    }
+
+    #[test]
+    fn string_too_long() {
+        let options = ParseOptions { max_string_length: Some(4), ..ParseOptions::default() };
+        let too_long = |input: &str| Tokenizer::new(input).with_options(&options).find_map(Result::err);
+
+        assert_eq!(too_long(r#"[1, "abcde"]"#), Some(TokenizeError::StringTooLong { location: Location::new(1, 5, 4) }));
+        assert_eq!(too_long(r#""ab\tcd""#), Some(TokenizeError::StringTooLong { location: Location::new(1, 1, 0) }));
+        assert_eq!(too_long(r#""ééé""#), Some(TokenizeError::StringTooLong { location: Location::new(1, 1, 0) }));
+    }
+
+    // single quotes
+    #[test]
+    fn single_quoted_strings() {
+        let options = ParseOptions { allow_single_quotes: true, ..ParseOptions::default() };
+
+        let actual: Vec<Token> = Tokenizer::new(r#"['it\'s', 'say "hi"', "\'"]"#)
+            .with_options(&options)
+            .map(|spanned| spanned.unwrap().token)
+            .collect();
+
+        assert_eq!(actual[1], Token::string("it's"));
+        assert_eq!(actual[3], Token::string("say \"hi\""));
+        assert_eq!(actual[5], Token::string("'"));
+    }
+
+    #[test]
+    fn unclosed_single_quotes() {
+        let options = ParseOptions { allow_single_quotes: true, ..ParseOptions::default() };
+
+        let actual = Tokenizer::new("'abc\"").with_options(&options).next().unwrap().unwrap_err();
+
+        assert_eq!(actual, TokenizeError::UnclosedQuotes { location: Location::new(1, 1, 0) });
+    }
+
+    #[test]
+    fn lenient_escapes() {
+        let options = ParseOptions { lenient_escapes: true, ..ParseOptions::default() };
+
+        let actual = Tokenizer::new(r#""a\vb\0c\x41\xe9""#).with_options(&options).next().unwrap().unwrap();
+
+        assert_eq!(actual.token, Token::string("a\u{b}b\0cAé"));
+    }
+
+    #[test]
+    fn lenient_escapes_invalid_hex() {
+        let options = ParseOptions { lenient_escapes: true, ..ParseOptions::default() };
+        let error = |input| Tokenizer::new(input).with_options(&options).next().unwrap().unwrap_err();
+
+        assert_eq!(error(r#""\x4g""#), TokenizeError::InvalidHexValue { location: Location::new(1, 2, 1) });
+        assert_eq!(error(r#""\x4"#), TokenizeError::UnfinishedEscape { location: Location::new(1, 2, 1) });
+    }
+
+    #[test]
+    fn lenient_escapes_rejected_by_default() {
+        for (input, ch) in [(r#""\v""#, 'v'), (r#""\0""#, '0'), (r#""\x41""#, 'x')] {
+            let actual = tokenize(String::from(input)).unwrap_err();
+            assert_eq!(actual, TokenizeError::InvalidEscape { ch, location: Location::new(1, 2, 1) });
+        }
+    }
+
+    #[test]
+    fn single_quotes_rejected_by_default() {
+        let actual = tokenize(String::from("'a'")).unwrap_err();
+        assert_eq!(actual, TokenizeError::CharNotRecognized { ch: '\'', location: Location::new(1, 1, 0) });
+
+        let actual = tokenize(String::from(r#""\'""#)).unwrap_err();
+        assert_eq!(actual, TokenizeError::InvalidEscape { ch: '\'', location: Location::new(1, 2, 1) });
+    }
+
+    // location
+    #[test]
+    fn char_not_recognized_location() {
+        let input = String::from("[\n  true,\n  ?]");
+        let expected = TokenizeError::CharNotRecognized { ch: '?', location: Location::new(3, 3, 12) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn location_after_multi_byte_chars() {
+        let input = String::from("[\"💩\", \"ü");
+        let expected = TokenizeError::UnclosedQuotes { location: Location::new(1, 7, 9) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn unfinished_literal_location() {
+        let input = String::from("[nul]");
+        let expected = TokenizeError::UnfinishedLiteralValue { location: Location::new(1, 5, 4) };
+
+        let actual = tokenize(input).unwrap_err();
+
+        assert_eq!(actual, expected);
+    }
+
+    // whitespace
+    #[test]
+    fn leading_whitespace() {
+        let actual = tokenize(String::from(" \t\r\n true")).unwrap();
+
+        assert_eq!(actual, [SpannedToken {
+            token: Token::True,
+            span: Span { start: Location::new(2, 2, 5), end: Location::new(2, 6, 9) },
+        }]);
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        assert_eq!(tokens(String::from("null \n\t")), [Token::Null]);
+    }
+
+    #[test]
+    fn interleaved_whitespace() {
+        let expected = [Token::LeftBrace, Token::string("a"), Token::Colon, Token::number(1), Token::RightBrace];
+
+        assert_eq!(tokens(String::from(" {\n\t\"a\" :\r\n1 }  ")), expected);
+    }
+
+    #[test]
+    fn whitespace_only() {
+        assert_eq!(tokens(String::from("")), []);
+        assert_eq!(tokens(String::from(" \n ")), []);
+    }
+
+    #[test]
+    fn non_ascii_whitespace_is_rejected() {
+        let actual = tokenize(String::from("\u{a0}1")).unwrap_err();
+
+        assert_eq!(actual, TokenizeError::CharNotRecognized { ch: '\u{a0}', location: Location::new(1, 1, 0) });
+    }
+
+    #[test]
+    fn leading_bom_is_skipped() {
+        let input = "\u{feff}[1]";
+        let expected = Span { start: Location::new(1, 1, 3), end: Location::new(1, 2, 4) };
+
+        let bytes: Vec<_> = Tokenizer::new(input).map(Result::unwrap).collect();
+        let chars: Vec<_> = Tokenizer::from_chars(input.chars()).map(Result::unwrap).collect();
+
+        assert_eq!(bytes[0].span, expected);
+        assert_eq!(bytes, chars);
+    }
+
+    #[test]
+    fn bom_in_the_middle_is_rejected() {
+        let actual = tokenize(String::from("[\u{feff}1]")).unwrap_err();
+
+        assert_eq!(actual, TokenizeError::CharNotRecognized { ch: '\u{feff}', location: Location::new(1, 2, 1) });
+    }
+
+    #[test]
+    fn only_one_bom_is_skipped() {
+        let actual = tokenize(String::from("\u{feff}\u{feff}1")).unwrap_err();
+
+        assert!(matches!(actual, TokenizeError::CharNotRecognized { ch: '\u{feff}', .. }));
+    }
+
+    #[test]
+    fn token_spans() {
+        let input = String::from("[\n  \"ü\"]");
+        let expected = [
+            Span { start: Location::new(1, 1, 0), end: Location::new(1, 2, 1) },
+            Span { start: Location::new(2, 3, 4), end: Location::new(2, 6, 8) },
+            Span { start: Location::new(2, 6, 8), end: Location::new(2, 7, 9) },
+        ];
+
+        let actual: Vec<Span> = tokenize(input)
+            .unwrap()
+            .into_iter()
+            .map(|spanned| spanned.span)
+            .collect();
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,399 @@
+use std::collections::HashSet;
+
+use crate::borrowed::{document_tokens, TokenRef};
+use crate::parse::{unexpected_token, StreamToken, TokenParseError};
+use crate::tokenize::Token;
+use crate::{DuplicateKeyPolicy, Location, Number, ParseError, ParseOptions, Span};
+
+/// Callbacks for [`parse_with_visitor`], each one does nothing unless it is implemented
+///
+/// They are called in document order: a container begins, its elements follow, each object
+/// value right after its key, and then the container ends.
+pub trait Visitor {
+    fn on_null(&mut self) {}
+
+    fn on_bool(&mut self, _value: bool) {}
+
+    fn on_number(&mut self, _number: Number) {}
+
+    /// Number literal as written, with [`ParseOptions::preserve_number_text`] or
+    /// [`ParseOptions::number_parser`], which is not called
+    fn on_raw_number(&mut self, _text: &str) {}
+
+    fn on_string(&mut self, _value: &str) {}
+
+    fn on_begin_array(&mut self) {}
+
+    fn on_end_array(&mut self) {}
+
+    fn on_begin_object(&mut self) {}
+
+    fn on_key(&mut self, _key: &str) {}
+
+    fn on_end_object(&mut self) {}
+}
+
+/// Ignores everything, which leaves only checking the input
+impl Visitor for () {}
+
+/// Parses a single value, reporting it to the visitor piece by piece instead of building a [`Value`](crate::Value)
+///
+/// Input is checked as strictly as [`parse_with`](crate::parse_with) checks it, but the visitor
+/// has already seen everything before the error when one is found.
+pub fn parse_with_visitor<V: Visitor>(input: &str, options: &ParseOptions, visitor: &mut V) -> Result<(), ParseError> {
+    let mut tokens = document_tokens(input, options)?;
+    let mut structure = Structure::default();
+
+    while let Some((token, span)) = tokens.next()? {
+        structure.push(token, span, options, visitor)?;
+    }
+
+    structure.finish()
+}
+
+/// Where a document is in its structure, moved forward one token at a time
+///
+/// Checks the tokens as strictly as `parse::parse_value` does, holding only the open containers.
+/// Tokens can be pushed as they arrive, which lets [`StreamParser`](crate::StreamParser) use it.
+#[derive(Debug, Default)]
+pub(crate) struct Structure {
+    state: State,
+    stack: Vec<Open>,
+}
+
+#[derive(Debug, Default)]
+enum State {
+    /// No token was pushed yet
+    #[default]
+    Start,
+    /// A value comes next, after a colon or a comma in an array
+    Value,
+    /// Right after `[`, the first element or `]` comes next
+    FirstElement,
+    /// Right after `{`, the first key or `}` comes next
+    FirstKey,
+    /// Right after an object key, holds the key and where it starts when it is a duplicate
+    Colon(Option<(String, Location)>),
+    /// An element ended, a comma or the closing bracket comes next
+    AfterValue,
+    /// Right after a comma, holds where it is
+    AfterComma(Location),
+    /// The top-level value is complete
+    Done,
+}
+
+/// Container that is still open, counting the elements started so far
+#[derive(Debug)]
+enum Open {
+    Array(usize),
+    /// Holds the keys so far when duplicates are errors
+    Object(usize, HashSet<String>),
+}
+
+impl Structure {
+    /// Whether a token was pushed, to tell input without any apart from input that ends too early
+    pub(crate) fn has_started(&self) -> bool {
+        !matches!(self.state, State::Start)
+    }
+
+    /// Moves past the token, telling the visitor what it is
+    pub(crate) fn push<V: Visitor>(
+        &mut self,
+        token: TokenRef<'_>,
+        span: Span,
+        options: &ParseOptions,
+        visitor: &mut V,
+    ) -> Result<(), ParseError> {
+        match std::mem::replace(&mut self.state, State::Done) {
+            State::Start | State::Value => self.value(token, span, options, visitor),
+            State::FirstElement | State::FirstKey if self.closes(&token) => {
+                self.close(visitor);
+                Ok(())
+            }
+            State::FirstElement => {
+                self.count_element(span, options)?;
+                self.value(token, span, options, visitor)
+            }
+            State::FirstKey => {
+                self.count_element(span, options)?;
+                self.key(token, span, options, visitor)
+            }
+            State::Colon(duplicate) => match token {
+                TokenRef::Other(Token::Colon) => {
+                    if let Some((key, location)) = duplicate {
+                        return Err(TokenParseError::DuplicateKey { key, location }.into());
+                    }
+                    self.state = State::Value;
+                    Ok(())
+                }
+                found => {
+                    let found = (found, span).to_token();
+                    Err(TokenParseError::ExpectedColon { found, location: span.start }.into())
+                }
+            },
+            State::AfterValue => match token {
+                TokenRef::Other(Token::Comma) => {
+                    self.state = State::AfterComma(span.start);
+                    Ok(())
+                }
+                token if self.closes(&token) => {
+                    self.close(visitor);
+                    Ok(())
+                }
+                found => {
+                    let found = (found, span).to_token();
+                    Err(TokenParseError::ExpectedComma { found, location: span.start }.into())
+                }
+            },
+            State::AfterComma(location) if self.closes(&token) => {
+                if !options.allow_trailing_commas {
+                    return Err(TokenParseError::TrailingComma { location }.into());
+                }
+                self.close(visitor);
+                Ok(())
+            }
+            State::AfterComma(_) => {
+                self.count_element(span, options)?;
+                match self.stack.last() {
+                    Some(Open::Object(..)) => self.key(token, span, options, visitor),
+                    _ => self.value(token, span, options, visitor),
+                }
+            }
+            State::Done => Err(ParseError::TrailingTokens),
+        }
+    }
+
+    /// Checks that the value is complete once there are no more tokens
+    pub(crate) fn finish(&self) -> Result<(), ParseError> {
+        match self.state {
+            State::Done => Ok(()),
+            _ => Err(TokenParseError::UnexpectedEof.into()),
+        }
+    }
+
+    fn value<V: Visitor>(&mut self, token: TokenRef<'_>, span: Span, options: &ParseOptions, visitor: &mut V) -> Result<(), ParseError> {
+        if matches!(token, TokenRef::Other(Token::LeftBracket | Token::LeftBrace)) && self.stack.len() >= options.max_depth {
+            return Err(TokenParseError::DepthLimitExceeded { location: span.start }.into());
+        }
+
+        match token {
+            TokenRef::String(string) => visitor.on_string(&string),
+            TokenRef::Other(Token::Null) => visitor.on_null(),
+            TokenRef::Other(Token::True) => visitor.on_bool(true),
+            TokenRef::Other(Token::False) => visitor.on_bool(false),
+            TokenRef::Other(Token::Number(number)) => visitor.on_number(number),
+            TokenRef::Other(Token::RawNumber(text)) => visitor.on_raw_number(&text),
+            TokenRef::Other(Token::LeftBracket) => {
+                visitor.on_begin_array();
+                self.stack.push(Open::Array(0));
+                self.state = State::FirstElement;
+                return Ok(());
+            }
+            TokenRef::Other(Token::LeftBrace) => {
+                visitor.on_begin_object();
+                self.stack.push(Open::Object(0, HashSet::new()));
+                self.state = State::FirstKey;
+                return Ok(());
+            }
+            TokenRef::Other(token) => return Err(unexpected_token(token, span.start, self.stack.is_empty()).into()),
+        }
+
+        self.end_value();
+        Ok(())
+    }
+
+    fn key<V: Visitor>(&mut self, token: TokenRef<'_>, span: Span, options: &ParseOptions, visitor: &mut V) -> Result<(), ParseError> {
+        let TokenRef::String(key) = token else {
+            return Err(TokenParseError::ExpectedProperty { location: span.start }.into());
+        };
+
+        visitor.on_key(&key);
+        let duplicate = match self.stack.last_mut() {
+            Some(Open::Object(_, keys)) if options.duplicate_keys == DuplicateKeyPolicy::Error && keys.contains(key.as_ref()) => {
+                Some((key.into_owned(), span.start))
+            }
+            Some(Open::Object(_, keys)) if options.duplicate_keys == DuplicateKeyPolicy::Error => {
+                keys.insert(key.into_owned());
+                None
+            }
+            _ => None,
+        };
+
+        self.state = State::Colon(duplicate);
+        Ok(())
+    }
+
+    /// Counts the element starting at `span`, failing there when there are too many
+    fn count_element(&mut self, span: Span, options: &ParseOptions) -> Result<(), ParseError> {
+        let (Some(Open::Array(length)) | Some(Open::Object(length, _))) = self.stack.last_mut() else {
+            unreachable!("elements are only counted in containers");
+        };
+
+        *length += 1;
+        if options.max_elements.is_some_and(|max| *length > max) {
+            return Err(TokenParseError::TooManyElements { location: span.start }.into());
+        }
+
+        Ok(())
+    }
+
+    /// Whether the token is the closing bracket of the innermost container
+    fn closes(&self, token: &TokenRef<'_>) -> bool {
+        match self.stack.last() {
+            Some(Open::Array(_)) => matches!(token, TokenRef::Other(Token::RightBracket)),
+            Some(Open::Object(..)) => matches!(token, TokenRef::Other(Token::RightBrace)),
+            None => false,
+        }
+    }
+
+    fn close<V: Visitor>(&mut self, visitor: &mut V) {
+        match self.stack.pop() {
+            Some(Open::Array(_)) => visitor.on_end_array(),
+            Some(Open::Object(..)) => visitor.on_end_object(),
+            None => unreachable!("only open containers are closed"),
+        }
+
+        self.end_value();
+    }
+
+    fn end_value(&mut self) {
+        self.state = if self.stack.is_empty() { State::Done } else { State::AfterValue };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::TokenParseError;
+    use crate::{parse_with, DuplicateKeyPolicy, Location, Number, ParseOptions};
+    use super::{parse_with_visitor, Visitor};
+
+    /// Writes every event down
+    #[derive(Default)]
+    struct Events(Vec<String>);
+
+    impl Visitor for Events {
+        fn on_null(&mut self) {
+            self.0.push(String::from("null"));
+        }
+
+        fn on_bool(&mut self, value: bool) {
+            self.0.push(format!("bool {value}"));
+        }
+
+        fn on_number(&mut self, number: Number) {
+            self.0.push(format!("number {number}"));
+        }
+
+        fn on_raw_number(&mut self, text: &str) {
+            self.0.push(format!("raw {text}"));
+        }
+
+        fn on_string(&mut self, value: &str) {
+            self.0.push(format!("string {value}"));
+        }
+
+        fn on_begin_array(&mut self) {
+            self.0.push(String::from("["));
+        }
+
+        fn on_end_array(&mut self) {
+            self.0.push(String::from("]"));
+        }
+
+        fn on_begin_object(&mut self) {
+            self.0.push(String::from("{"));
+        }
+
+        fn on_key(&mut self, key: &str) {
+            self.0.push(format!("key {key}"));
+        }
+
+        fn on_end_object(&mut self) {
+            self.0.push(String::from("}"));
+        }
+    }
+
+    fn events(input: &str, options: &ParseOptions) -> Vec<String> {
+        let mut events = Events::default();
+        parse_with_visitor(input, options, &mut events).unwrap();
+
+        events.0
+    }
+
+    #[test]
+    fn reports_events_in_order() {
+        let actual = events(r#"{"a": [1, true, null, []], "b": {}, "c": "x"}"#, &ParseOptions::default());
+
+        assert_eq!(actual, [
+            "{", "key a", "[", "number 1", "bool true", "null", "[", "]", "]", "key b", "{", "}", "key c", "string x", "}",
+        ]);
+        assert_eq!(events("2.50", &ParseOptions { preserve_number_text: true, ..ParseOptions::default() }), ["raw 2.50"]);
+    }
+
+    #[test]
+    fn picks_a_single_field() {
+        /// Keeps the `id` of the top-level object, ignoring the rest
+        #[derive(Default)]
+        struct Id {
+            depth: usize,
+            at_id: bool,
+            id: Option<Number>,
+        }
+
+        impl Visitor for Id {
+            fn on_number(&mut self, number: Number) {
+                if self.at_id {
+                    self.id = Some(number);
+                }
+            }
+
+            fn on_begin_array(&mut self) {
+                self.depth += 1;
+            }
+
+            fn on_end_array(&mut self) {
+                self.depth -= 1;
+            }
+
+            fn on_begin_object(&mut self) {
+                self.depth += 1;
+            }
+
+            fn on_key(&mut self, key: &str) {
+                self.at_id = self.depth == 1 && key == "id";
+            }
+
+            fn on_end_object(&mut self) {
+                self.depth -= 1;
+            }
+        }
+
+        let mut visitor = Id::default();
+        parse_with_visitor(r#"{"nested": {"id": 1}, "list": [2], "id": 3}"#, &ParseOptions::default(), &mut visitor).unwrap();
+
+        assert_eq!(visitor.id, Some(Number::from(3)));
+    }
+
+    #[test]
+    fn reports_same_errors_as_parse() {
+        let options = ParseOptions { max_depth: 2, max_elements: Some(2), ..ParseOptions::default() };
+        let inputs = ["", "[1,", r#"{"a" 1}"#, "[1 2]", "[1,]", "1 2", "}", "[[[]]]", "[1, 2, 3]", r#"{"a": 1 "b"}"#];
+
+        for input in inputs {
+            let expected = parse_with(String::from(input), &options).map(drop);
+            assert_eq!(parse_with_visitor(input, &options, &mut Events::default()), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let input = r#"{"a": 1, "a": 2}"#;
+        let options = ParseOptions { duplicate_keys: DuplicateKeyPolicy::Error, ..ParseOptions::default() };
+
+        assert_eq!(
+            parse_with_visitor(input, &options, &mut Events::default()),
+            Err(TokenParseError::DuplicateKey { key: String::from("a"), location: Location::new(1, 10, 9) }.into()),
+        );
+        assert_eq!(events(input, &ParseOptions::default()), ["{", "key a", "number 1", "key a", "number 2", "}"]);
+    }
+}
@@ -0,0 +1,189 @@
+use std::fmt;
+
+use crate::Value;
+
+/// Step from a container to one of its elements
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathSegment<'a> {
+    /// Key of an object entry
+    Key(&'a str),
+    /// Index of an array element
+    Index(usize),
+}
+
+impl fmt::Display for PathSegment<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(key) => f.write_str(key),
+            Self::Index(index) => write!(f, "{index}"),
+        }
+    }
+}
+
+impl Value {
+    /// Calls `f` for every value in the tree, this one included, with the path leading to it from here
+    ///
+    /// Containers are visited before their elements, and elements in order.
+    pub fn walk<'a, F: FnMut(&[PathSegment<'a>], &'a Value)>(&'a self, mut f: F) {
+        walk(self, &mut Vec::new(), &mut f);
+    }
+
+    /// Calls `f` for every value in the tree, this one included, letting it change them in place
+    ///
+    /// Like [`Value::walk`] containers are visited before their elements, so `f` sees the elements
+    /// of whatever it left in place of a container, and none if it replaced the container with a scalar.
+    pub fn walk_mut<F: FnMut(&mut Value)>(&mut self, mut f: F) {
+        walk_mut(self, &mut f);
+    }
+
+    /// Lists every leaf with its path from here, like `("user.tags.0", &Value::String("admin"))`
+    ///
+    /// Paths join object keys and array indices with `.`, keys are written as they are so a key
+    /// holding a `.` reads like two segments. Scalars and empty arrays and objects are leaves,
+    /// a scalar at the root has the empty path.
+    pub fn flatten(&self) -> Vec<(String, &Value)> {
+        let mut leaves = Vec::new();
+        self.walk(|path, value| {
+            if value.is_empty().unwrap_or(true) {
+                let path: Vec<String> = path.iter().map(ToString::to_string).collect();
+                leaves.push((path.join("."), value));
+            }
+        });
+
+        leaves
+    }
+}
+
+fn walk<'a, F: FnMut(&[PathSegment<'a>], &'a Value)>(value: &'a Value, path: &mut Vec<PathSegment<'a>>, f: &mut F) {
+    f(path, value);
+
+    match value {
+        Value::Array(array) => {
+            for (index, element) in array.iter().enumerate() {
+                path.push(PathSegment::Index(index));
+                walk(element, path, f);
+                path.pop();
+            }
+        }
+        Value::Object(map) => {
+            for (key, value) in map {
+                path.push(PathSegment::Key(key));
+                walk(value, path, f);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn walk_mut<F: FnMut(&mut Value)>(value: &mut Value, f: &mut F) {
+    f(value);
+
+    match value {
+        Value::Array(array) => array.iter_mut().for_each(|element| walk_mut(element, f)),
+        Value::Object(map) => map.values_mut().for_each(|value| walk_mut(value, f)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+    use super::PathSegment::{self, Index, Key};
+
+    #[test]
+    fn visits_every_value_in_order() {
+        let value = json!({"user": {"tags": ["admin", null]}});
+        let mut visited: Vec<(Vec<PathSegment>, &Value)> = Vec::new();
+
+        value.walk(|path, value| visited.push((path.to_vec(), value)));
+
+        assert_eq!(visited, vec![
+            (vec![], &value),
+            (vec![Key("user")], &value["user"]),
+            (vec![Key("user"), Key("tags")], &value["user"]["tags"]),
+            (vec![Key("user"), Key("tags"), Index(0)], &value["user"]["tags"][0]),
+            (vec![Key("user"), Key("tags"), Index(1)], &Value::Null),
+        ]);
+    }
+
+    #[test]
+    fn finds_long_strings() {
+        let value = json!(["short", {"text": "a longer string"}, []]);
+        let mut found = Vec::new();
+
+        value.walk(|path, value| {
+            if value.as_str().is_some_and(|string| string.len() > 5) {
+                found.push(path.to_vec());
+            }
+        });
+
+        assert_eq!(found, vec![vec![Index(1), Key("text")]]);
+    }
+
+    #[test]
+    fn scalar_root() {
+        let mut count = 0;
+
+        json!(true).walk(|path, _| {
+            assert!(path.is_empty());
+            count += 1;
+        });
+
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn lowercases_every_string() {
+        let mut value = json!({"Name": "ADA", "tags": ["X", {"inner": "Y"}], "n": 1});
+
+        value.walk_mut(|value| {
+            if let Value::String(string) = value {
+                *string = string.to_lowercase();
+            }
+        });
+
+        assert_eq!(value, json!({"Name": "ada", "tags": ["x", {"inner": "y"}], "n": 1}));
+    }
+
+    #[test]
+    fn redacts_fields() {
+        let mut value = json!({"user": {"password": {"hash": "abc"}, "login": "ada"}, "password": "x"});
+        let mut visited = 0;
+
+        value.walk_mut(|value| {
+            visited += 1;
+            if let Value::Object(map) = value
+                && let Some(password) = map.get_mut("password")
+            {
+                *password = Value::String(String::from("***"));
+            }
+        });
+
+        assert_eq!(value, json!({"user": {"password": "***", "login": "ada"}, "password": "***"}));
+        // the replaced objects are not descended into
+        assert_eq!(visited, 5);
+    }
+
+    #[test]
+    fn flattens_leaves() {
+        let value = json!({"user": {"tags": ["admin", true], "name": "ada"}, "empty": {}, "list": [], "n": null});
+
+        let actual = value.flatten();
+
+        assert_eq!(actual, vec![
+            (String::from("user.tags.0"), &Value::String(String::from("admin"))),
+            (String::from("user.tags.1"), &Value::Boolean(true)),
+            (String::from("user.name"), &Value::String(String::from("ada"))),
+            (String::from("empty"), &json!({})),
+            (String::from("list"), &Value::Array(vec![])),
+            (String::from("n"), &Value::Null),
+        ]);
+    }
+
+    #[test]
+    fn flattens_scalar_root() {
+        assert_eq!(json!(1).flatten(), vec![(String::new(), &json!(1))]);
+        assert_eq!(json!([]).flatten(), vec![(String::new(), &json!([]))]);
+    }
+}